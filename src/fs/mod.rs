@@ -4,6 +4,10 @@ use std::{fs, path::PathBuf};
 /// Operations pertaining to underlying inodes of files.
 pub mod inode;
 
+/// Names of the users and groups that own files.
+#[cfg(unix)]
+pub mod owner;
+
 /// Unix file permissions.
 #[cfg(unix)]
 pub mod permissions;
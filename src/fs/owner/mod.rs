@@ -0,0 +1,65 @@
+use libc::{c_char, c_int, gid_t, group, passwd, uid_t, ERANGE};
+use std::{ffi::CStr, mem::MaybeUninit, ptr};
+
+/// Unit tests for looking up the names of users and groups.
+#[cfg(test)]
+mod test;
+
+/// Size of the buffer for the strings of a database entry to start out with, which is grown for
+/// entries that don't fit.
+const INITIAL_BUF_LEN: usize = 1024;
+
+/// Upper bound on the size of the buffer for the strings of a database entry.
+const MAX_BUF_LEN: usize = 1 << 20;
+
+/// Looks up the name of the user with `uid` in the passwd database.
+pub fn user_name(uid: uid_t) -> Option<String> {
+    lookup(
+        |entry: *mut passwd, buf, len, result| unsafe {
+            libc::getpwuid_r(uid, entry, buf, len, result)
+        },
+        |entry| entry.pw_name,
+    )
+}
+
+/// Looks up the name of the group with `gid` in the group database.
+pub fn group_name(gid: gid_t) -> Option<String> {
+    lookup(
+        |entry: *mut group, buf, len, result| unsafe {
+            libc::getgrgid_r(gid, entry, buf, len, result)
+        },
+        |entry| entry.gr_name,
+    )
+}
+
+/// Calls one of the reentrant database lookups such as `getpwuid_r`, retrying with a larger
+/// buffer as long as the entry doesn't fit, and copies out the name that `name_of` points to.
+/// Returns `None` if there is no such entry.
+fn lookup<T, F>(getter: F, name_of: fn(&T) -> *const c_char) -> Option<String>
+where
+    F: Fn(*mut T, *mut c_char, usize, *mut *mut T) -> c_int,
+{
+    let mut buf = vec![0 as c_char; INITIAL_BUF_LEN];
+
+    loop {
+        let mut entry = MaybeUninit::<T>::uninit();
+        let mut result = ptr::null_mut();
+
+        let ret = getter(entry.as_mut_ptr(), buf.as_mut_ptr(), buf.len(), &mut result);
+
+        if ret == ERANGE && buf.len() < MAX_BUF_LEN {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+
+        if ret != 0 || result.is_null() {
+            return None;
+        }
+
+        // The strings of the entry live in `buf` so the name has to be copied out before it's
+        // dropped.
+        let name = unsafe { CStr::from_ptr(name_of(&*result)) };
+
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
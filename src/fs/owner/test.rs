@@ -0,0 +1,7 @@
+use super::{group_name, user_name};
+
+#[test]
+fn root_names() {
+    assert_eq!(user_name(0).as_deref(), Some("root"));
+    assert!(group_name(0).is_some());
+}
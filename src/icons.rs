@@ -1,13 +1,11 @@
-use crate::hash;
+use crate::{hash, render::context::file::FileType};
 use ansi_term::Style;
 use ansi_term::{ANSIGenericString, Color};
-use ignore::DirEntry;
 use once_cell::sync::Lazy;
 use std::{
     borrow::Cow,
     collections::HashMap,
     ffi::{OsStr, OsString},
-    fs::FileType,
     path::Path,
 };
 
@@ -19,20 +17,18 @@ use std::{
 ///
 /// If a directory entry is a link and the link target is provided, the link target will be
 /// used to determine the icon.
-pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str> {
-    let icon = entry
-        .file_type()
-        .and_then(icon_from_file_type)
-        .map(Cow::from);
+pub fn compute(
+    path: &Path,
+    file_type: Option<FileType>,
+    link_target: Option<&Path>,
+) -> Cow<'static, str> {
+    let icon = file_type.and_then(icon_from_file_type).map(Cow::from);
 
     if let Some(i) = icon {
         return i;
     }
 
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
+    let ext = link_target.map_or_else(|| path.extension(), Path::extension);
 
     let icon = ext.and_then(icon_from_ext).map(|(_, i)| Cow::from(i));
 
@@ -40,7 +36,10 @@ pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str
         return i;
     }
 
-    let icon = icon_from_file_name(entry.file_name()).map(Cow::from);
+    let icon = path
+        .file_name()
+        .and_then(icon_from_file_name)
+        .map(Cow::from);
 
     if let Some(i) = icon {
         return i;
@@ -51,14 +50,12 @@ pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str
 
 /// Computes a plain, colored icon with given parameters. See [compute] for more details.
 pub fn compute_with_color(
-    entry: &DirEntry,
+    path: &Path,
+    file_type: Option<FileType>,
     link_target: Option<&Path>,
     style: Option<Style>,
 ) -> Cow<'static, str> {
-    let icon = entry
-        .file_type()
-        .and_then(icon_from_file_type)
-        .map(Cow::from);
+    let icon = file_type.and_then(icon_from_file_type).map(Cow::from);
 
     let paint_icon = |icon| match style {
         Some(Style {
@@ -76,10 +73,7 @@ pub fn compute_with_color(
         return paint_icon(icon);
     }
 
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
+    let ext = link_target.map_or_else(|| path.extension(), Path::extension);
 
     let icon = ext
         .and_then(icon_from_ext)
@@ -89,7 +83,9 @@ pub fn compute_with_color(
         return i;
     }
 
-    let icon = icon_from_file_name(entry.file_name())
+    let icon = path
+        .file_name()
+        .and_then(icon_from_file_name)
         .map(Cow::from)
         .map(paint_icon);
 
@@ -109,13 +105,11 @@ fn icon_from_ext(ext: &OsStr) -> Option<(u8, &'static str)> {
 
 /// Attempts to return an icon based on file type.
 fn icon_from_file_type(ft: FileType) -> Option<&'static str> {
-    if ft.is_dir() {
-        return FILE_TYPE_ICON_MAP.get("dir").copied();
-    } else if ft.is_symlink() {
-        return FILE_TYPE_ICON_MAP.get("symlink").copied();
+    match ft {
+        FileType::Dir => FILE_TYPE_ICON_MAP.get("dir").copied(),
+        FileType::Link => FILE_TYPE_ICON_MAP.get("symlink").copied(),
+        FileType::File => None,
     }
-
-    None
 }
 
 /// Attempts to get the icon associated with the special file kind.
@@ -21,13 +21,20 @@
 )]
 use clap::CommandFactory;
 use render::{
-    context::Context,
+    context::{format::OutputFormat, Context},
     tree::{
-        display::{Flat, Inverted, Regular},
-        Tree,
+        diff::Diff,
+        display::{
+            Breakdown, Csv, Dot, Flat, Folded, Html, Inverted, Json, Ncdu, Ndjson, Regular, Svg,
+            Top, Tsv,
+        },
+        interrupt, Tree,
     },
 };
-use std::{io::stdout, process::ExitCode};
+use std::{
+    io::{stdout, BufWriter},
+    process::ExitCode,
+};
 
 /// Operations to wrangle ANSI escaped strings.
 mod ansi;
@@ -44,15 +51,26 @@ mod render;
 /// Utilities relating to interacting with tty properties.
 mod tty;
 
+/// Interactive full-screen terminal browser.
+mod tui;
+
 /// Common utilities across all modules.
 mod utils;
 
+/// Redrawing the tree as the filesystem changes.
+mod watch;
+
 fn main() -> ExitCode {
     if let Err(e) = run() {
         eprintln!("{e}");
         return ExitCode::FAILURE;
     }
 
+    if interrupt::is_interrupted() {
+        eprintln!("Traversal was interrupted; the output is incomplete.");
+        return ExitCode::from(interrupt::EXIT_CODE);
+    }
+
     ExitCode::SUCCESS
 }
 
@@ -64,9 +82,81 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    render::styles::init(ctx.no_color());
+    // The treemap's palette is derived from `LS_COLORS` even if stdout isn't a tty.
+    let plain = if ctx.format == Some(OutputFormat::Svg) {
+        ctx.no_color
+    } else {
+        ctx.no_color()
+    };
+
+    render::styles::init(plain);
 
-    if ctx.flat {
+    if ctx.interactive {
+        tui::run(ctx)?;
+    } else if ctx.watch {
+        if ctx.top.is_some() {
+            watch::run::<Top>(ctx)?;
+        } else if ctx.breakdown() {
+            watch::run::<Breakdown>(ctx)?;
+        } else if ctx.flat {
+            watch::run::<Flat>(ctx)?;
+        } else if ctx.inverted {
+            watch::run::<Inverted>(ctx)?;
+        } else {
+            watch::run::<Regular>(ctx)?;
+        }
+    } else if let Some(baseline) = ctx.diff.clone() {
+        let diff = Diff::try_init(ctx, &baseline)?;
+        println!("{diff}");
+    } else if let Some(format) = ctx.format {
+        match format {
+            OutputFormat::Json => {
+                let tree = Tree::<Json>::try_init(ctx)?;
+                println!("{tree}");
+            }
+            OutputFormat::Ndjson if Tree::<Ndjson>::can_stream(&ctx) => {
+                Tree::<Ndjson>::stream(&ctx, BufWriter::new(stdout().lock()))?;
+            }
+            OutputFormat::Ndjson => {
+                let tree = Tree::<Ndjson>::try_init(ctx)?;
+                print!("{tree}");
+            }
+            OutputFormat::Csv => {
+                let tree = Tree::<Csv>::try_init(ctx)?;
+                print!("{tree}");
+            }
+            OutputFormat::Tsv => {
+                let tree = Tree::<Tsv>::try_init(ctx)?;
+                print!("{tree}");
+            }
+            OutputFormat::Html => {
+                let tree = Tree::<Html>::try_init(ctx)?;
+                println!("{tree}");
+            }
+            OutputFormat::Ncdu => {
+                let tree = Tree::<Ncdu>::try_init(ctx)?;
+                println!("{tree}");
+            }
+            OutputFormat::Dot => {
+                let tree = Tree::<Dot>::try_init(ctx)?;
+                println!("{tree}");
+            }
+            OutputFormat::Svg => {
+                let tree = Tree::<Svg>::try_init(ctx)?;
+                println!("{tree}");
+            }
+            OutputFormat::Folded => {
+                let tree = Tree::<Folded>::try_init(ctx)?;
+                print!("{tree}");
+            }
+        }
+    } else if ctx.top.is_some() {
+        let tree = Tree::<Top>::try_init(ctx)?;
+        print!("{tree}");
+    } else if ctx.breakdown() {
+        let tree = Tree::<Breakdown>::try_init(ctx)?;
+        print!("{tree}");
+    } else if ctx.flat {
         let tree = Tree::<Flat>::try_init(ctx)?;
         println!("{tree}");
     } else if ctx.inverted {
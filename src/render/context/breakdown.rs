@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// How regular files are grouped when summarizing their disk usage with `--breakdown`.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// Group files by their extension, ignoring case
+    #[default]
+    Ext,
+
+    /// Group files by the icon they are shown with, which is shared by related extensions
+    Icon,
+}
@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+
+/// Order in which to print entries when comparing against a snapshot.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub enum DiffSort {
+    /// Sort entries by how many bytes they grew or shrank
+    #[default]
+    Abs,
+
+    /// Sort entries by how much they grew or shrank relative to their previous size
+    Rel,
+}
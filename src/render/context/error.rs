@@ -10,6 +10,12 @@ pub enum Error {
     #[error("A configuration file was found but failed to parse: {0}")]
     Config(#[source] ClapError),
 
+    #[error("'--both-sizes' cannot be used with '--disk-usage inodes'")]
+    BothSizesOfInodes,
+
+    #[error("'--own-blocks' can only be used with '--disk-usage physical' or '--both-sizes'")]
+    OwnBlocksWithoutPhysical,
+
     #[error("No glob was provided")]
     EmptyGlob,
 
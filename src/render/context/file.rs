@@ -1,7 +1,11 @@
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
 
 /// File-types found in both Unix and Windows.
-#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(
+    Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
 #[allow(clippy::module_name_repetitions)]
 pub enum FileType {
     /// A regular file.
@@ -14,3 +18,16 @@ pub enum FileType {
     /// A symlink.
     Link,
 }
+
+/// Symlinks that are followed take on the file-type of their target.
+impl From<fs::FileType> for FileType {
+    fn from(ft: fs::FileType) -> Self {
+        if ft.is_dir() {
+            Self::Dir
+        } else if ft.is_symlink() {
+            Self::Link
+        } else {
+            Self::File
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+
+/// Alternative output formats, typically machine-readable, that can be used in place of the
+/// default tree presentation.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)]
+pub enum OutputFormat {
+    /// Nested JSON document of the entire tree
+    Json,
+
+    /// Newline-delimited JSON with one record per entry
+    Ndjson,
+
+    /// Comma-separated values with a header row
+    Csv,
+
+    /// Tab-separated values with a header row
+    Tsv,
+
+    /// Self-contained interactive HTML report
+    Html,
+
+    /// ncdu JSON export which can be browsed with `ncdu -f`
+    Ncdu,
+
+    /// Graphviz DOT graph of the directory hierarchy
+    Dot,
+
+    /// Squarified treemap of disk usage as SVG
+    Svg,
+
+    /// Folded stacks for flame graph tools such as inferno or flamegraph.pl
+    Folded,
+}
@@ -0,0 +1,15 @@
+use clap::ValueEnum;
+
+/// How the size of a file with multiple hard links is charged to the directories containing them.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Accounting {
+    /// Count the file once at whichever of its links comes first in name order
+    #[default]
+    First,
+
+    /// Count the file in full at every one of its links
+    Every,
+
+    /// Split the size of the file evenly among its links within the tree
+    Split,
+}
@@ -1,14 +1,16 @@
 use super::disk_usage::{file_size::DiskUsage, units::PrefixKind};
 use crate::tty;
+use breakdown::Grouping;
 use clap::{parser::ValueSource, ArgMatches, CommandFactory, FromArgMatches, Id, Parser};
+use diff::DiffSort;
 use error::Error;
 use file::FileType;
-use ignore::{
-    overrides::{Override, OverrideBuilder},
-    DirEntry,
-};
+use format::OutputFormat;
+use hardlink::Accounting;
+use ignore::overrides::{Override, OverrideBuilder};
 use output::ColumnProperties;
 use regex::Regex;
+use size::{Cutoff, Threshold};
 use sort::SortType;
 use std::{
     borrow::Borrow,
@@ -17,18 +19,37 @@ use std::{
     path::{Path, PathBuf},
 };
 
+/// Grouping of files to summarize their disk usage.
+pub mod breakdown;
+
 /// Operations to load in defaults from configuration file.
 pub mod config;
 
+/// Printing order of comparisons against a snapshot.
+pub mod diff;
+
 /// [Context] related errors.
 pub mod error;
 
 /// Common cross-platform file-types.
 pub mod file;
 
+/// Alternative output formats.
+pub mod format;
+
+/// Accounting of files with multiple hard links.
+pub mod hardlink;
+
 /// Utilities to print output.
 pub mod output;
 
+/// Owners of files to go by.
+#[cfg(unix)]
+pub mod owner;
+
+/// Sizes given on the command-line.
+pub mod size;
+
 /// Printing order kinds.
 pub mod sort;
 
@@ -40,6 +61,9 @@ pub mod time;
 #[cfg(test)]
 mod test;
 
+/// Predicate used to filter entries given their path and file-type.
+pub type Predicate = Box<dyn Fn(&Path, Option<FileType>) -> bool + Send + Sync + 'static>;
+
 /// Defines the CLI.
 #[derive(Parser, Debug)]
 #[command(name = "erdtree")]
@@ -54,10 +78,30 @@ pub struct Context {
     #[arg(short = 'C', long)]
     pub force_color: bool,
 
-    /// Print physical or logical file size
+    /// Print physical or logical file size, or the number of inodes
     #[arg(short, long, value_enum, default_value_t = DiskUsage::default())]
     pub disk_usage: DiskUsage,
 
+    /// Print logical and physical file size side by side
+    #[arg(long, conflicts_with = "suppress_size")]
+    pub both_sizes: bool,
+
+    /// Print the ratio of physical to logical file size alongside both of them
+    #[arg(long, requires = "both_sizes")]
+    pub ratio: bool,
+
+    /// Include the blocks allocated to directories and symlinks themselves in physical disk usage like du
+    #[arg(long)]
+    pub own_blocks: bool,
+
+    /// How to account for the size of files with multiple hard links
+    #[arg(long, value_enum, default_value_t = Accounting::default())]
+    pub hardlinks: Accounting,
+
+    /// Print how many bytes each entry shares through hard links with entries outside of it
+    #[arg(long, conflicts_with = "suppress_size")]
+    pub shared: bool,
+
     /// Follow symlinks
     #[arg(short = 'f', long)]
     pub follow: bool,
@@ -66,10 +110,54 @@ pub struct Context {
     #[arg(short = 'F', long)]
     pub flat: bool,
 
+    /// Print output in an alternative, machine-readable format
+    #[arg(long, value_enum, conflicts_with_all = ["flat", "inverted"])]
+    pub format: Option<OutputFormat>,
+
+    /// Only print the NUM largest files anywhere in the tree, ranked by size
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["format", "flat", "inverted", "diff", "interactive"])]
+    pub top: Option<usize>,
+
+    /// Rank directories rather than files when using --top
+    #[arg(long, requires = "top")]
+    pub top_dirs: bool,
+
+    /// Only print the disk usage of regular files summed up by extension or by icon
+    #[arg(long, value_enum, value_name = "GROUPING", conflicts_with_all = ["format", "flat", "inverted", "diff", "interactive", "top", "collapse"])]
+    pub breakdown: Option<Grouping>,
+
     /// Print disk usage in human-readable format
     #[arg(short = 'H', long)]
     pub human: bool,
 
+    /// Read the tree from an ncdu JSON export instead of traversing the filesystem
+    #[arg(long, value_name = "FILE")]
+    pub import_ncdu: Option<PathBuf>,
+
+    /// Render a snapshot saved with --save instead of traversing the filesystem
+    #[arg(long, value_name = "FILE", conflicts_with = "import_ncdu")]
+    pub load: Option<PathBuf>,
+
+    /// Save a snapshot of the tree to FILE that can be rendered again later with --load
+    #[arg(long, value_name = "FILE", conflicts_with = "pattern")]
+    pub save: Option<PathBuf>,
+
+    /// Compare against a snapshot saved with --save and show what grew, shrank, appeared, or vanished
+    #[arg(long, value_name = "SNAPSHOT", conflicts_with_all = ["format", "flat"])]
+    pub diff: Option<PathBuf>,
+
+    /// Sort-order to display changes when using --diff
+    #[arg(long, value_enum, default_value_t = DiffSort::default())]
+    pub diff_sort: DiffSort,
+
+    /// Browse the tree interactively in a full-screen terminal interface
+    #[arg(long, conflicts_with_all = ["format", "flat", "diff"])]
+    pub interactive: bool,
+
+    /// Keep running and redraw the tree whenever files under the root directory change
+    #[arg(long, conflicts_with_all = ["format", "diff", "interactive", "load", "import_ncdu"])]
+    pub watch: bool,
+
     /// Do not respect .gitignore files
     #[arg(short = 'i', long)]
     pub no_ignore: bool,
@@ -88,11 +176,31 @@ pub struct Context {
     #[arg(long, requires = "long")]
     pub octal: bool,
 
+    /// Show each entry's share of its parent directory and of the root as percentages and a bar
+    #[arg(long, conflicts_with = "suppress_size")]
+    pub percent: bool,
+
+    /// Show the number of files, directories, and links that each directory contains at any depth
+    #[arg(long)]
+    pub counts: bool,
+
     /// Which kind of timestamp to use; modified by default
     #[cfg(unix)]
     #[arg(long, value_enum, requires = "long")]
     pub time: Option<time::Stamp>,
 
+    /// Show the user or group that owns each file and most of each directory's disk usage; both
+    /// if given twice or as "user,group"
+    #[cfg(unix)]
+    #[arg(long, value_enum, value_delimiter = ',', requires = "long")]
+    pub owner: Vec<owner::Owner>,
+
+    /// Only print the disk usage of regular files summed up by the user or group that owns them;
+    /// one table for each if given twice or as "user,group"
+    #[cfg(unix)]
+    #[arg(long, value_enum, value_name = "OWNER", value_delimiter = ',', conflicts_with_all = ["format", "flat", "inverted", "diff", "interactive", "top", "collapse", "breakdown"])]
+    pub owners: Vec<owner::Owner>,
+
     /// Maximum depth to display
     #[arg(short = 'L', long, value_name = "NUM")]
     level: Option<usize>,
@@ -117,6 +225,18 @@ pub struct Context {
     #[arg(short = 'P', long)]
     pub prune: bool,
 
+    /// Only show entries at least this large, e.g. 500M or 2GiB; bare prefixes follow '--unit'
+    #[arg(long, value_name = "SIZE")]
+    min_size: Option<Threshold>,
+
+    /// Only show entries at most this large, e.g. 500M or 2GiB; bare prefixes follow '--unit'
+    #[arg(long, value_name = "SIZE")]
+    max_size: Option<Threshold>,
+
+    /// Merge entries smaller than THRESHOLD, a share of their directory like 1% or a size like 10M, into a single entry
+    #[arg(long, value_name = "THRESHOLD")]
+    pub collapse: Option<Cutoff>,
+
     /// Sort-order to display directory content
     #[arg(short, long, value_enum, default_value_t = SortType::default())]
     pub sort: SortType,
@@ -172,18 +292,22 @@ pub struct Context {
     //////////////////////////
     /* INTERNAL USAGE BELOW */
     //////////////////////////
-    /// Is stdin in a tty?
-    #[clap(skip = tty::stdin_is_tty())]
-    pub stdin_is_tty: bool,
-
-    /// Is stdin in a tty?
+    /// Is stdout in a tty?
     #[clap(skip = tty::stdout_is_tty())]
     pub stdout_is_tty: bool,
 
+    /// Is stderr in a tty?
+    #[clap(skip = tty::stderr_is_tty())]
+    pub stderr_is_tty: bool,
+
     /// Restricts column width of size not including units
     #[clap(skip = usize::default())]
     pub max_size_width: usize,
 
+    /// Restricts column width of the numbers of descendants
+    #[clap(skip = usize::default())]
+    pub max_count_width: usize,
+
     /// Restricts column width of disk_usage units
     #[clap(skip = usize::default())]
     pub max_size_unit_width: usize,
@@ -203,6 +327,16 @@ pub struct Context {
     #[cfg(unix)]
     pub max_block_width: usize,
 
+    /// Restricts column width of the owning user for long view
+    #[clap(skip = usize::default())]
+    #[cfg(unix)]
+    pub max_user_width: usize,
+
+    /// Restricts column width of the owning group for long view
+    #[clap(skip = usize::default())]
+    #[cfg(unix)]
+    pub max_group_width: usize,
+
     /// Width of the terminal emulator's window
     #[clap(skip)]
     pub window_width: Option<usize>,
@@ -212,6 +346,28 @@ impl Context {
     /// Initializes [Context], optionally reading in the configuration file to override defaults.
     /// Arguments provided will take precedence over config.
     pub fn init() -> Result<Self, Error> {
+        let mut ctx = Self::from_args()?;
+
+        #[cfg(unix)]
+        {
+            ctx.owner = crate::utils::uniq(ctx.owner);
+            ctx.owners = crate::utils::uniq(ctx.owners);
+        }
+
+        if ctx.both_sizes && ctx.disk_usage == DiskUsage::Inodes {
+            return Err(Error::BothSizesOfInodes);
+        }
+
+        // Only physical disk usage accounts for blocks, which `--both-sizes` shows either way.
+        if ctx.own_blocks && !ctx.both_sizes && ctx.disk_usage != DiskUsage::Physical {
+            return Err(Error::OwnBlocksWithoutPhysical);
+        }
+
+        Ok(ctx)
+    }
+
+    /// Parses the command-line arguments reconciled with those of the configuration file.
+    fn from_args() -> Result<Self, Error> {
         let user_args = Self::command().args_override_self(true).get_matches();
 
         let no_config = user_args
@@ -294,6 +450,12 @@ impl Context {
             .map_or_else(|| Path::new("."), |pb| pb.as_path())
     }
 
+    /// Sets the root directory. Used when the tree originates from a source other than the
+    /// filesystem.
+    pub fn set_dir(&mut self, dir: PathBuf) {
+        self.dir = Some(dir);
+    }
+
     /// Returns canonical [Path] of the root directory to be traversed.
     pub fn dir_canonical(&self) -> PathBuf {
         std::fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf())
@@ -311,6 +473,27 @@ impl Context {
         self.time.unwrap_or_default()
     }
 
+    /// The smallest size in bytes of entries to show, if any.
+    pub fn min_size(&self) -> Option<u64> {
+        self.min_size.map(|threshold| threshold.bytes(self.unit))
+    }
+
+    /// The largest size in bytes of entries to show, if any.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size.map(|threshold| threshold.bytes(self.unit))
+    }
+
+    /// Whether to print a breakdown of disk usage by extension, icon, or owner in place of the
+    /// tree.
+    pub fn breakdown(&self) -> bool {
+        #[cfg(unix)]
+        if !self.owners.is_empty() {
+            return true;
+        }
+
+        self.breakdown.is_some()
+    }
+
     /// Which filetype to filter on; defaults to regular file.
     pub fn file_type(&self) -> FileType {
         self.file_type.unwrap_or_default()
@@ -338,9 +521,7 @@ impl Context {
     /// to the root node somehow. Empty sets not producing an output is handled by [`Tree`].
     ///
     /// [`Tree`]: crate::render::tree::Tree
-    pub fn regex_predicate(
-        &self,
-    ) -> Result<Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>, Error> {
+    pub fn regex_predicate(&self) -> Result<Predicate, Error> {
         let Some(pattern) = self.pattern.as_ref() else {
             return Err(Error::PatternNotProvided);
         };
@@ -350,42 +531,43 @@ impl Context {
         let file_type = self.file_type();
 
         match file_type {
-            FileType::Dir => Ok(Box::new(move |dir_entry: &DirEntry| {
-                let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
+            FileType::Dir => Ok(Box::new(
+                move |path: &Path, entry_type: Option<FileType>| {
+                    let is_dir = entry_type == Some(FileType::Dir);
 
-                if is_dir {
-                    // Problem right here.
-                    return Self::ancestor_regex_match(dir_entry.path(), &re, 0);
-                }
+                    if is_dir {
+                        // Problem right here.
+                        return Self::ancestor_regex_match(path, &re, 0);
+                    }
 
-                Self::ancestor_regex_match(dir_entry.path(), &re, 1)
-            })),
+                    Self::ancestor_regex_match(path, &re, 1)
+                },
+            )),
 
-            _ => Ok(Box::new(move |dir_entry: &DirEntry| {
-                let entry_type = dir_entry.file_type();
-                let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
+            _ => Ok(Box::new(
+                move |path: &Path, entry_type: Option<FileType>| {
+                    let is_dir = entry_type == Some(FileType::Dir);
 
-                if is_dir {
-                    return true;
-                }
+                    if is_dir {
+                        return true;
+                    }
 
-                match file_type {
-                    FileType::File if entry_type.map_or(true, |ft| !ft.is_file()) => return false,
-                    FileType::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
-                        return false
+                    match file_type {
+                        FileType::File if entry_type != Some(FileType::File) => return false,
+                        FileType::Link if entry_type != Some(FileType::Link) => return false,
+                        _ => (),
                     }
-                    _ => (),
-                }
-                let file_name = dir_entry.file_name().to_string_lossy();
-                re.is_match(&file_name)
-            })),
+                    let file_name = path
+                        .file_name()
+                        .map_or_else(|| path.to_string_lossy(), OsStr::to_string_lossy);
+                    re.is_match(&file_name)
+                },
+            )),
         }
     }
 
     /// Predicate used for filtering via globs and file-types.
-    pub fn glob_predicate(
-        &self,
-    ) -> Result<Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>, Error> {
+    pub fn glob_predicate(&self) -> Result<Predicate, Error> {
         let mut builder = OverrideBuilder::new(self.dir());
 
         let mut negated_glob = false;
@@ -415,49 +597,64 @@ impl Context {
         let file_type = self.file_type();
 
         match file_type {
-            FileType::Dir => Ok(Box::new(move |dir_entry: &DirEntry| {
-                let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
+            FileType::Dir => Ok(Box::new(
+                move |path: &Path, entry_type: Option<FileType>| {
+                    let is_dir = entry_type == Some(FileType::Dir);
+
+                    if is_dir {
+                        if negated_glob {
+                            return !Self::ancestor_glob_match(path, &overrides, 0);
+                        } else {
+                            return Self::ancestor_glob_match(path, &overrides, 0);
+                        }
+                    }
+                    let matched = Self::ancestor_glob_match(path, &overrides, 1);
 
-                if is_dir {
                     if negated_glob {
-                        return !Self::ancestor_glob_match(dir_entry.path(), &overrides, 0);
+                        !matched
                     } else {
-                        return Self::ancestor_glob_match(dir_entry.path(), &overrides, 0);
+                        matched
                     }
-                }
-                let matched = Self::ancestor_glob_match(dir_entry.path(), &overrides, 1);
+                },
+            )),
 
-                if negated_glob {
-                    !matched
-                } else {
-                    matched
-                }
-            })),
+            _ => Ok(Box::new(
+                move |path: &Path, entry_type: Option<FileType>| {
+                    let is_dir = entry_type == Some(FileType::Dir);
 
-            _ => Ok(Box::new(move |dir_entry: &DirEntry| {
-                let entry_type = dir_entry.file_type();
-                let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
+                    if is_dir {
+                        return true;
+                    }
 
-                if is_dir {
-                    return true;
-                }
+                    match file_type {
+                        FileType::File if entry_type != Some(FileType::File) => return false,
+                        FileType::Link if entry_type != Some(FileType::Link) => return false,
+                        _ => (),
+                    }
 
-                match file_type {
-                    FileType::File if entry_type.map_or(true, |ft| !ft.is_file()) => return false,
-                    FileType::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
-                        return false
+                    let matched = overrides.matched(path, false);
+
+                    if negated_glob {
+                        !matched.is_whitelist()
+                    } else {
+                        matched.is_whitelist()
                     }
-                    _ => (),
-                }
+                },
+            )),
+        }
+    }
 
-                let matched = overrides.matched(dir_entry.path(), false);
+    /// Predicate used for filtering entries based off of `--pattern`. Returns `None` if no
+    /// pattern was provided.
+    pub fn pattern_predicate(&self) -> Result<Option<Predicate>, Error> {
+        if self.pattern.is_none() {
+            return Ok(None);
+        }
 
-                if negated_glob {
-                    !matched.is_whitelist()
-                } else {
-                    matched.is_whitelist()
-                }
-            })),
+        if self.glob || self.iglob {
+            self.glob_predicate().map(Some)
+        } else {
+            self.regex_predicate().map(Some)
         }
     }
 
@@ -476,12 +673,15 @@ impl Context {
     pub fn update_column_properties(&mut self, col_props: &ColumnProperties) {
         self.max_size_width = col_props.max_size_width;
         self.max_size_unit_width = col_props.max_size_unit_width;
+        self.max_count_width = col_props.max_count_width;
 
         #[cfg(unix)]
         {
             self.max_nlink_width = col_props.max_nlink_width;
             self.max_block_width = col_props.max_block_width;
             self.max_ino_width = col_props.max_ino_width;
+            self.max_user_width = col_props.max_user_width;
+            self.max_group_width = col_props.max_group_width;
         }
     }
 
@@ -1,4 +1,4 @@
-use super::{Context, PrefixKind};
+use super::{Context, DiskUsage, PrefixKind};
 use std::convert::From;
 
 /// Utility struct to help store maximum column widths for attributes of each node. Each width is
@@ -6,6 +6,7 @@ use std::convert::From;
 pub struct ColumnProperties {
     pub max_size_width: usize,
     pub max_size_unit_width: usize,
+    pub max_count_width: usize,
 
     #[cfg(unix)]
     pub max_nlink_width: usize,
@@ -15,11 +16,18 @@ pub struct ColumnProperties {
 
     #[cfg(unix)]
     pub max_block_width: usize,
+
+    #[cfg(unix)]
+    pub max_user_width: usize,
+
+    #[cfg(unix)]
+    pub max_group_width: usize,
 }
 
 impl From<&Context> for ColumnProperties {
     fn from(ctx: &Context) -> Self {
         let unit_width = match ctx.unit {
+            _ if ctx.disk_usage == DiskUsage::Inodes => 1,
             PrefixKind::Si => 2,
             PrefixKind::Bin => 3,
         };
@@ -27,12 +35,17 @@ impl From<&Context> for ColumnProperties {
         Self {
             max_size_width: 0,
             max_size_unit_width: unit_width,
+            max_count_width: 0,
             #[cfg(unix)]
             max_nlink_width: 0,
             #[cfg(unix)]
             max_ino_width: 0,
             #[cfg(unix)]
             max_block_width: 0,
+            #[cfg(unix)]
+            max_user_width: 0,
+            #[cfg(unix)]
+            max_group_width: 0,
         }
     }
 }
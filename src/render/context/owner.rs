@@ -0,0 +1,25 @@
+use crate::fs::owner;
+use clap::ValueEnum;
+
+/// Whether to go by the user or the group that owns files.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Hash, Default)]
+pub enum Owner {
+    /// The user that owns files
+    #[default]
+    User,
+
+    /// The group that owns files
+    Group,
+}
+
+impl Owner {
+    /// The name of the user or group with `id`, or the id itself if it has no name.
+    pub fn name_of(self, id: u32) -> String {
+        let name = match self {
+            Self::User => owner::user_name(id),
+            Self::Group => owner::group_name(id),
+        };
+
+        name.unwrap_or_else(|| id.to_string())
+    }
+}
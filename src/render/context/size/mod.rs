@@ -0,0 +1,113 @@
+use crate::render::disk_usage::units::{BinPrefix, PrefixKind, SiPrefix, UnitPrefix};
+use std::str::FromStr;
+
+/// Unit tests for parsing sizes and cutoffs.
+#[cfg(test)]
+mod test;
+
+/// A size given on the command-line such as `500M`, `1.5GB`, or `2GiB`. Prefixes ending in `B`
+/// are SI, those ending in `iB` binary, and bare ones like `M` follow `--unit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Threshold {
+    num: f64,
+    magnitude: usize,
+    prefix_kind: Option<PrefixKind>,
+}
+
+impl Threshold {
+    /// The threshold in bytes with bare prefixes interpreted according to `unit`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn bytes(&self, unit: PrefixKind) -> u64 {
+        let base_value = match self.prefix_kind.unwrap_or(unit) {
+            PrefixKind::Si => SiPrefix::nth(self.magnitude).base_value(),
+            PrefixKind::Bin => BinPrefix::nth(self.magnitude).base_value(),
+        };
+
+        (self.num * base_value as f64).round() as u64
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+        let (num, prefix) = s.split_at(split);
+
+        let num = num
+            .trim_end()
+            .parse::<f64>()
+            .ok()
+            .filter(|num| num.is_finite() && *num >= 0.0)
+            .ok_or_else(|| format!("'{s}' does not start with a non-negative number"))?;
+
+        let prefix = prefix.to_ascii_lowercase();
+        let mut chars = prefix.chars();
+
+        let magnitude = match chars.next() {
+            None | Some('b') if prefix.len() <= 1 => 0,
+            Some('k') => 1,
+            Some('m') => 2,
+            Some('g') => 3,
+            Some('t') => 4,
+            _ => return Err(format!("'{s}' has an unknown unit")),
+        };
+
+        let prefix_kind = match chars.as_str() {
+            _ if magnitude == 0 => None,
+            "" => None,
+            "b" => Some(PrefixKind::Si),
+            "ib" => Some(PrefixKind::Bin),
+            _ => return Err(format!("'{s}' has an unknown unit")),
+        };
+
+        Ok(Self {
+            num,
+            magnitude,
+            prefix_kind,
+        })
+    }
+}
+
+/// The size under which entries are merged by `--collapse`, either a percentage of the size of
+/// their parent directory like `1%` or a [Threshold] like `10M`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cutoff {
+    Percent(f64),
+    Size(Threshold),
+}
+
+impl Cutoff {
+    /// Is `bytes` at or above the cutoff given the size of the parent directory, `parent_bytes`, with
+    /// bare prefixes interpreted according to `unit`?
+    #[allow(clippy::cast_precision_loss)]
+    pub fn is_above(&self, bytes: u64, parent_bytes: u64, unit: PrefixKind) -> bool {
+        match self {
+            Self::Percent(percent) => bytes as f64 >= parent_bytes as f64 * percent / 100.0,
+            Self::Size(threshold) => bytes >= threshold.bytes(unit),
+        }
+    }
+}
+
+impl FromStr for Cutoff {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(percent) = s.trim().strip_suffix('%') else {
+            return s.parse().map(Self::Size);
+        };
+
+        percent
+            .trim_end()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| (0.0..=100.0).contains(percent))
+            .map(Self::Percent)
+            .ok_or_else(|| format!("'{s}' is not a percentage between 0% and 100%"))
+    }
+}
@@ -0,0 +1,40 @@
+use super::{Cutoff, Threshold};
+use crate::render::disk_usage::units::PrefixKind;
+
+#[test]
+fn parse_threshold() {
+    let bytes = |s: &str, unit| s.parse::<Threshold>().map(|t| t.bytes(unit));
+
+    assert_eq!(bytes("512", PrefixKind::Bin), Ok(512));
+    assert_eq!(bytes("512B", PrefixKind::Bin), Ok(512));
+    assert_eq!(bytes("500M", PrefixKind::Bin), Ok(500 * 2_u64.pow(20)));
+    assert_eq!(bytes("500M", PrefixKind::Si), Ok(500 * 10_u64.pow(6)));
+    assert_eq!(bytes("1.5 GB", PrefixKind::Bin), Ok(1_500_000_000));
+    assert_eq!(bytes("2GiB", PrefixKind::Si), Ok(2 * 2_u64.pow(30)));
+    assert_eq!(bytes("1kib", PrefixKind::Si), Ok(1024));
+
+    assert!(bytes("", PrefixKind::Bin).is_err());
+    assert!(bytes("-1K", PrefixKind::Bin).is_err());
+    assert!(bytes("1X", PrefixKind::Bin).is_err());
+    assert!(bytes("1KiBs", PrefixKind::Bin).is_err());
+    assert!(bytes("1bB", PrefixKind::Bin).is_err());
+}
+
+#[test]
+fn parse_cutoff() {
+    assert_eq!("1.5%".parse(), Ok(Cutoff::Percent(1.5)));
+    assert_eq!("10M".parse(), Ok(Cutoff::Size("10M".parse().unwrap())));
+
+    assert!("101%".parse::<Cutoff>().is_err());
+    assert!("x%".parse::<Cutoff>().is_err());
+
+    let percent = Cutoff::Percent(10.0);
+
+    assert!(percent.is_above(10, 100, PrefixKind::Bin));
+    assert!(!percent.is_above(9, 100, PrefixKind::Bin));
+
+    let size = "1K".parse::<Cutoff>().unwrap();
+
+    assert!(size.is_above(1024, 0, PrefixKind::Bin));
+    assert!(!size.is_above(1023, 0, PrefixKind::Bin));
+}
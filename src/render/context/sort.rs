@@ -13,4 +13,10 @@ pub enum SortType {
 
     /// Sort entries by size largest to smallest, bottom to top
     SizeRev,
+
+    /// Sort entries by number of descendants smallest to largest, top to bottom
+    Count,
+
+    /// Sort entries by number of descendants largest to smallest, bottom to top
+    CountRev,
 }
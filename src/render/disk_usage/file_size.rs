@@ -6,13 +6,13 @@ use crate::{
 use ansi_term::Style;
 use clap::ValueEnum;
 use filesize::PathExt;
+use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, fs::Metadata, ops::AddAssign, path::Path};
 
 /// Represents either logical or physical size and handles presentation.
 #[derive(Clone, Debug)]
 pub struct FileSize {
     pub bytes: u64,
-    #[allow(dead_code)]
     disk_usage: DiskUsage,
     prefix_kind: PrefixKind,
     human_readable: bool,
@@ -29,7 +29,7 @@ pub struct FileSize {
 }
 
 /// Determines between logical or physical size for display
-#[derive(Copy, Clone, Debug, ValueEnum, Default)]
+#[derive(Copy, Clone, Debug, ValueEnum, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DiskUsage {
     /// How many bytes does a file contain
     Logical,
@@ -37,6 +37,20 @@ pub enum DiskUsage {
     /// How much actual space on disk, taking into account sparse files and compression.
     #[default]
     Physical,
+
+    /// How many inodes an entry takes up, which for a directory includes all of its contents
+    Inodes,
+}
+
+impl DiskUsage {
+    /// The kind of size that isn't `self`. Inodes have no counterpart.
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Logical => Self::Physical,
+            Self::Physical => Self::Logical,
+            Self::Inodes => Self::Inodes,
+        }
+    }
 }
 
 impl FileSize {
@@ -65,6 +79,11 @@ impl FileSize {
         Self::new(bytes, DiskUsage::Logical, human_readable, prefix_kind)
     }
 
+    /// A single inode.
+    pub const fn inode(prefix_kind: PrefixKind, human_readable: bool) -> Self {
+        Self::new(1, DiskUsage::Inodes, human_readable, prefix_kind)
+    }
+
     /// Computes the physical size of a file given its [Path] and [Metadata].
     pub fn physical(
         path: &Path,
@@ -77,10 +96,29 @@ impl FileSize {
             .map(|bytes| Self::new(bytes, DiskUsage::Physical, human_readable, prefix_kind))
     }
 
+    /// The [DiskUsage] that [FileSize] is a measure of.
+    pub const fn disk_usage(&self) -> DiskUsage {
+        self.disk_usage
+    }
+
+    /// The [Style] from the disk usage theme that befits the magnitude of [FileSize]. Only
+    /// available after [Self::precompute_unpadded_display].
+    pub const fn style(&self) -> Option<&'static Style> {
+        self.style
+    }
+
     pub fn unpadded_display(&self) -> Option<&str> {
         self.unpadded_display.as_deref()
     }
 
+    /// Plain, unstyled human-readable representation of [FileSize] irrespective of whether or not
+    /// it was initialized to be human-readable.
+    pub fn human_readable_display(&self) -> String {
+        let mut human = Self::new(self.bytes, self.disk_usage, true, self.prefix_kind);
+        human.precompute_unpadded_display();
+        human.unpadded_display.unwrap_or_default()
+    }
+
     /// Precompute the raw (unpadded) display and sets the number of columns the size (without
     /// the prefix) will occupy. Also sets the [Style] to use in advance to style the size output.
     #[allow(clippy::cast_possible_truncation)]
@@ -88,6 +126,11 @@ impl FileSize {
     pub fn precompute_unpadded_display(&mut self) {
         let fbytes = self.bytes as f64;
 
+        if self.disk_usage == DiskUsage::Inodes {
+            self.precompute_unpadded_count_display();
+            return;
+        }
+
         match self.prefix_kind {
             PrefixKind::Si => {
                 let unit = SiPrefix::from(fbytes);
@@ -136,15 +179,57 @@ impl FileSize {
         }
     }
 
+    /// Precomputes the display of a number of inodes which, unlike bytes, goes without a unit and
+    /// is always scaled using SI prefixes if human-readable.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn precompute_unpadded_count_display(&mut self) {
+        let fcount = self.bytes as f64;
+        let unit = SiPrefix::from(fcount);
+
+        if !self.human_readable || matches!(unit, SiPrefix::Base) {
+            self.unpadded_display = Some(format!("{}", self.bytes));
+            self.size_columns = utils::num_integral(self.bytes);
+            self.uses_base_unit = Some(());
+        } else {
+            let size = fcount / (unit.base_value() as f64);
+            self.unpadded_display = Some(format!("{size:.2} {}", unit.count_display()));
+            self.size_columns = utils::num_integral((size * 100.0).floor() as u64) + 1;
+        }
+
+        if let Ok(theme) = get_du_theme() {
+            let style = theme.get(format!("{unit}").as_str());
+            self.style = style;
+        }
+    }
+
     /// Formats [FileSize] for presentation.
     pub fn format(&self, max_size_width: usize, max_size_unit_width: usize) -> String {
-        let out = if self.human_readable {
+        let out = if self.disk_usage == DiskUsage::Inodes {
+            if !self.human_readable {
+                format!("{:>max_size_width$}", self.bytes)
+            } else if self.uses_base_unit.is_some() {
+                format!(
+                    "{:>max_size_width$} {:max_size_unit_width$}",
+                    self.bytes, ""
+                )
+            } else {
+                let mut precomputed = self.unpadded_display().unwrap().split(' ');
+                let size = precomputed.next().unwrap();
+                let prefix = precomputed.next().unwrap();
+
+                format!("{size:>max_size_width$} {prefix:>max_size_unit_width$}")
+            }
+        } else if self.human_readable {
             let mut precomputed = self.unpadded_display().unwrap().split(' ');
             let size = precomputed.next().unwrap();
             let unit = precomputed.next().unwrap();
 
             if self.uses_base_unit.is_some() {
-                format!("{:>max_size_width$} {unit:>max_size_unit_width$}", self.bytes)
+                format!(
+                    "{:>max_size_width$} {unit:>max_size_unit_width$}",
+                    self.bytes
+                )
             } else {
                 format!("{size:>max_size_width$} {unit:>max_size_unit_width$}")
             }
@@ -173,6 +258,7 @@ impl FileSize {
         let placeholder_padding = placeholder.len()
             + ctx.max_size_width
             + match ctx.unit {
+                _ if ctx.disk_usage == DiskUsage::Inodes => 0,
                 PrefixKind::Si if ctx.human => 2,
                 PrefixKind::Bin if ctx.human => 3,
                 PrefixKind::Si => 0,
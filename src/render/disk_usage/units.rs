@@ -5,7 +5,7 @@ use std::{
 };
 
 /// Determines whether to use SI prefixes or binary prefixes.
-#[derive(Copy, Clone, Debug, ValueEnum, Default)]
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, Default)]
 pub enum PrefixKind {
     /// Displays disk usage using binary prefixes.
     #[default]
@@ -63,6 +63,43 @@ impl UnitPrefix for BinPrefix {
     }
 }
 
+impl BinPrefix {
+    /// The prefix that scales by 1024 to the power of `n`, saturating at [Self::Tebi].
+    pub const fn nth(n: usize) -> Self {
+        match n {
+            0 => Self::Base,
+            1 => Self::Kibi,
+            2 => Self::Mebi,
+            3 => Self::Gibi,
+            _ => Self::Tebi,
+        }
+    }
+}
+
+impl SiPrefix {
+    /// The prefix that scales by 1000 to the power of `n`, saturating at [Self::Tera].
+    pub const fn nth(n: usize) -> Self {
+        match n {
+            0 => Self::Base,
+            1 => Self::Kilo,
+            2 => Self::Mega,
+            3 => Self::Giga,
+            _ => Self::Tera,
+        }
+    }
+
+    /// The prefix on its own to scale counts of things other than bytes with.
+    pub const fn count_display(&self) -> &'static str {
+        match self {
+            Self::Base => "",
+            Self::Kilo => "K",
+            Self::Mega => "M",
+            Self::Giga => "G",
+            Self::Tera => "T",
+        }
+    }
+}
+
 /// Get the closest human-readable unit prefix for value.
 impl From<f64> for BinPrefix {
     fn from(value: f64) -> Self {
@@ -1,4 +1,6 @@
 use super::Node;
+use crate::render::disk_usage::units::{SiPrefix, UnitPrefix};
+use serde::Serialize;
 use std::{
     convert::From,
     fmt::{self, Display},
@@ -6,17 +8,35 @@ use std::{
 
 /// For keeping track of the number of various file-types of [Node]'s chlidren.
 #[allow(clippy::module_name_repetitions)]
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize)]
 pub struct FileCount {
+    #[serde(rename = "dirs")]
     pub num_dirs: usize,
+
+    #[serde(rename = "files")]
     pub num_files: usize,
+
+    #[serde(rename = "links")]
     pub num_links: usize,
 }
 
 impl FileCount {
-    /// Update [Self] with information from [Node].
+    /// A count of no entries at all.
+    pub const fn new() -> Self {
+        Self {
+            num_dirs: 0,
+            num_files: 0,
+            num_links: 0,
+        }
+    }
+
+    /// Update [Self] with information from [Node]. A synthetic [Node] standing in for entries
+    /// merged by `--collapse` counts those entries along with everything beneath them.
     pub fn update(&mut self, node: &Node) {
-        if node.is_dir() {
+        if let Some(collapsed) = node.collapsed() {
+            self.update_from_count(collapsed);
+            self.update_from_count(node.descendants());
+        } else if node.is_dir() {
             self.num_dirs += 1;
         } else if node.is_symlink() {
             self.num_links += 1;
@@ -25,6 +45,28 @@ impl FileCount {
         }
     }
 
+    /// Total number of entries of all file-types.
+    pub const fn total(&self) -> usize {
+        self.num_dirs + self.num_files + self.num_links
+    }
+
+    /// Formats a number of entries, scaled using SI prefixes if `human` is `true`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn format_num(num: usize, human: bool) -> String {
+        let fnum = num as f64;
+        let prefix = SiPrefix::from(fnum);
+
+        if !human || matches!(prefix, SiPrefix::Base) {
+            return format!("{num}");
+        }
+
+        format!(
+            "{:.1}{}",
+            fnum / prefix.base_value() as f64,
+            prefix.count_display()
+        )
+    }
+
     /// Update [Self] with information from [Self].
     pub fn update_from_count(
         &mut self,
@@ -38,6 +80,20 @@ impl FileCount {
         self.num_links += num_links;
         self.num_files += num_files;
     }
+
+    /// Update [Self] such that the entries counted by the provided [Self] no longer count.
+    pub fn remove_count(
+        &mut self,
+        Self {
+            num_dirs,
+            num_files,
+            num_links,
+        }: Self,
+    ) {
+        self.num_dirs = self.num_dirs.saturating_sub(num_dirs);
+        self.num_links = self.num_links.saturating_sub(num_links);
+        self.num_files = self.num_files.saturating_sub(num_files);
+    }
 }
 
 impl From<Vec<Self>> for FileCount {
@@ -0,0 +1,346 @@
+use super::{display::Regular, error::Error, node::Node, snapshot, Result, Tree};
+use crate::render::{
+    context::{diff::DiffSort, output::ColumnProperties, Context},
+    disk_usage::file_size::FileSize,
+    styles::{self, ThemesMap},
+};
+use ansi_term::{Color, Style};
+use indextree::{Arena, NodeId};
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
+
+/// The union of the [Tree] at hand and the [Tree] from a previously saved snapshot. Entries that
+/// are identical in both are pruned away.
+pub struct Diff {
+    arena: Arena<Entry>,
+    root_id: NodeId,
+    ctx: Context,
+}
+
+/// A single entry of [Diff] along with its size before and after.
+struct Entry {
+    name: String,
+    depth: usize,
+    old: Option<u64>,
+    new: Option<u64>,
+    style: Option<Style>,
+}
+
+/// How an [Entry] changed between the two trees.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+/// A rendered row of [Diff] prior to alignment.
+struct Row {
+    delta: String,
+    size: String,
+    growth: Ordering,
+    prefix: String,
+    name: String,
+}
+
+impl Diff {
+    /// Constructs the current [Tree], either by traversal or from `--load`, and compares it to
+    /// the snapshot at `baseline`, which is the one provided via `--diff`. A current [Tree] with
+    /// nothing beneath the root is fine as everything having been removed is a change like any.
+    pub fn try_init(ctx: Context, baseline: &Path) -> Result<Self> {
+        let Tree {
+            arena: new_arena,
+            root_id: new_root,
+            ctx,
+            ..
+        } = Tree::<Regular>::try_assemble(ctx)?;
+
+        let (_, nodes) = snapshot::load(baseline, &ctx)?;
+
+        let mut column_properties = ColumnProperties::from(&ctx);
+
+        let (mut old_arena, old_root) =
+            Tree::<Regular>::from_nodes(nodes, &ctx, &mut column_properties)?;
+
+        Tree::<Regular>::finalize(&mut old_arena, old_root, &mut column_properties, &ctx);
+
+        let mut arena = Arena::new();
+
+        let root_name = new_arena[new_root]
+            .get()
+            .file_name()
+            .to_string_lossy()
+            .into_owned();
+
+        let mut merge = Merge {
+            out: &mut arena,
+            old: &old_arena,
+            new: &new_arena,
+            sort: ctx.diff_sort,
+        };
+
+        let root_id = merge.merge(Some(old_root), Some(new_root), root_name, 0, true);
+
+        Ok(Self {
+            arena,
+            root_id: root_id.ok_or(Error::MissingRoot)?,
+            ctx,
+        })
+    }
+
+    /// Renders every [Entry] within `--level` in pre-order along with its tree prefix.
+    fn rows(
+        &self,
+        node_id: NodeId,
+        base_prefix: &str,
+        is_last: bool,
+        theme: &ThemesMap,
+        rows: &mut Vec<Row>,
+    ) {
+        let entry = self.arena[node_id].get();
+
+        if entry.depth > self.ctx.level() {
+            return;
+        }
+
+        let (prefix, child_prefix) = if entry.depth == 0 {
+            (String::new(), String::new())
+        } else if is_last {
+            let corner = if self.ctx.inverted { "uprt" } else { "drt" };
+            let prefix = format!("{base_prefix}{}", theme.get(corner).unwrap());
+            (prefix, format!("{base_prefix}{}", styles::SEP))
+        } else {
+            let prefix = format!("{base_prefix}{}", theme.get("vtrt").unwrap());
+            (prefix, format!("{base_prefix}{}", theme.get("vt").unwrap()))
+        };
+
+        rows.push(self.row(entry, prefix));
+
+        let mut children = node_id.children(&self.arena).peekable();
+
+        while let Some(child_id) = children.next() {
+            let is_last = children.peek().is_none();
+            self.rows(child_id, &child_prefix, is_last, theme, rows);
+        }
+    }
+
+    /// Renders a single [Entry].
+    fn row(&self, entry: &Entry, prefix: String) -> Row {
+        let delta = entry.delta();
+
+        let sign = match delta.cmp(&0) {
+            Ordering::Greater => "+",
+            Ordering::Less => "-",
+            Ordering::Equal => "",
+        };
+
+        let delta_display = format!("{sign}{}", self.size_display(delta.unsigned_abs()));
+
+        let size = entry.new.map_or_else(
+            || String::from(styles::PLACEHOLDER),
+            |bytes| self.size_display(bytes),
+        );
+
+        let marker = match entry.status() {
+            Status::Added => " [+]",
+            Status::Removed => " [-]",
+            Status::Changed | Status::Unchanged => "",
+        };
+
+        let name = entry.style.and_then(|style| style.foreground).map_or_else(
+            || entry.name.clone(),
+            |fg| fg.bold().paint(&entry.name).to_string(),
+        );
+
+        Row {
+            delta: delta_display,
+            size,
+            growth: delta.cmp(&0),
+            prefix,
+            name: format!("{name}{marker}"),
+        }
+    }
+
+    /// Unpadded display of `bytes` according to `--human` and `--unit`.
+    fn size_display(&self, bytes: u64) -> String {
+        let ctx = &self.ctx;
+        let mut file_size = FileSize::new(bytes, ctx.disk_usage, ctx.human, ctx.unit);
+        file_size.precompute_unpadded_display();
+        file_size.unpadded_display().unwrap_or_default().to_owned()
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let theme = styles::get_tree_theme().map_err(|_| fmt::Error)?;
+
+        let mut rows = vec![];
+
+        self.rows(self.root_id, "", true, theme, &mut rows);
+
+        if !self.ctx.inverted {
+            rows.reverse();
+        }
+
+        let delta_width = rows.iter().map(|r| r.delta.len()).max().unwrap_or(0);
+        let size_width = rows.iter().map(|r| r.size.len()).max().unwrap_or(0);
+        let no_color = self.ctx.no_color();
+
+        for Row {
+            delta,
+            size,
+            growth,
+            prefix,
+            name,
+        } in rows
+        {
+            let delta = format!("{delta:>delta_width$}");
+
+            let delta = match growth {
+                Ordering::Greater if !no_color => Color::Green.paint(delta).to_string(),
+                Ordering::Less if !no_color => Color::Red.paint(delta).to_string(),
+                _ => delta,
+            };
+
+            writeln!(f, "{delta} {size:>size_width$} {prefix}{name}")?;
+        }
+
+        let (mut added, mut removed, mut changed) = (0, 0, 0);
+
+        for node_id in self.root_id.descendants(&self.arena).skip(1) {
+            match self.arena[node_id].get().status() {
+                Status::Added => added += 1,
+                Status::Removed => removed += 1,
+                Status::Changed => changed += 1,
+                Status::Unchanged => (),
+            }
+        }
+
+        write!(f, "\n{added} added, {removed} removed, {changed} changed")
+    }
+}
+
+impl Entry {
+    /// Change in size in bytes.
+    #[allow(clippy::cast_possible_wrap)]
+    fn delta(&self) -> i64 {
+        self.new.unwrap_or(0) as i64 - self.old.unwrap_or(0) as i64
+    }
+
+    /// Change in size relative to the previous size. Entries that came into existence or were
+    /// previously empty have grown infinitely.
+    #[allow(clippy::cast_precision_loss)]
+    fn relative_delta(&self) -> f64 {
+        match self.old {
+            Some(old) if old > 0 => self.delta() as f64 / old as f64,
+            _ if self.delta() == 0 => 0.0,
+            _ => f64::INFINITY,
+        }
+    }
+
+    /// How the [Entry] changed.
+    fn status(&self) -> Status {
+        match (self.old, self.new) {
+            (None, _) => Status::Added,
+            (_, None) => Status::Removed,
+            (old, new) if old == new => Status::Unchanged,
+            _ => Status::Changed,
+        }
+    }
+}
+
+/// State used while recursively merging the previous and current trees.
+struct Merge<'a> {
+    out: &'a mut Arena<Entry>,
+    old: &'a Arena<Node>,
+    new: &'a Arena<Node>,
+    sort: DiffSort,
+}
+
+impl Merge<'_> {
+    /// Merges the entry at `old_id` and/or `new_id` along with all of their descendants into the
+    /// output arena. Returns `None` if neither the entry nor any of its descendants changed unless
+    /// it's the root.
+    fn merge(
+        &mut self,
+        old_id: Option<NodeId>,
+        new_id: Option<NodeId>,
+        name: String,
+        depth: usize,
+        is_root: bool,
+    ) -> Option<NodeId> {
+        let old_node = old_id.map(|id| self.old[id].get());
+        let new_node = new_id.map(|id| self.new[id].get());
+
+        let size = |node: &Node| node.file_size().map_or(0, |fs| fs.bytes);
+
+        let entry = Entry {
+            name,
+            depth,
+            old: old_node.map(size),
+            new: new_node.map(size),
+            style: new_node.or(old_node).and_then(Node::style),
+        };
+
+        let mut children: BTreeMap<OsString, (Option<NodeId>, Option<NodeId>)> = BTreeMap::new();
+
+        for child_id in old_id.into_iter().flat_map(|id| id.children(self.old)) {
+            let child_name = self.old[child_id].get().file_name().to_owned();
+            children.entry(child_name).or_default().0 = Some(child_id);
+        }
+
+        for child_id in new_id.into_iter().flat_map(|id| id.children(self.new)) {
+            let child_name = self.new[child_id].get().file_name().to_owned();
+            children.entry(child_name).or_default().1 = Some(child_id);
+        }
+
+        let unchanged = entry.status() == Status::Unchanged;
+
+        let node_id = self.out.new_node(entry);
+
+        let mut merged = children
+            .into_iter()
+            .filter_map(|(child_name, (old, new))| {
+                let child_name = child_name.to_string_lossy().into_owned();
+                self.merge(old, new, child_name, depth + 1, false)
+            })
+            .collect::<Vec<_>>();
+
+        if unchanged && merged.is_empty() && !is_root {
+            node_id.remove(self.out);
+            return None;
+        }
+
+        merged.sort_by(|a, b| self.compare(*a, *b));
+
+        for child_id in merged {
+            node_id.append(child_id, self.out);
+        }
+
+        Some(node_id)
+    }
+
+    /// Largest changes first. The display reverses this for the default bottom-up view such that
+    /// the largest changes end up closest to their parent directory.
+    fn compare(&self, a: NodeId, b: NodeId) -> Ordering {
+        let a = self.out[a].get();
+        let b = self.out[b].get();
+
+        let ord = match self.sort {
+            DiffSort::Abs => b.delta().unsigned_abs().cmp(&a.delta().unsigned_abs()),
+            DiffSort::Rel => b
+                .relative_delta()
+                .abs()
+                .partial_cmp(&a.relative_delta().abs())
+                .unwrap_or(Ordering::Equal),
+        };
+
+        ord.then_with(|| a.name.cmp(&b.name))
+    }
+}
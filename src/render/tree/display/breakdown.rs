@@ -0,0 +1,208 @@
+use crate::{
+    icons,
+    render::{
+        context::{breakdown::Grouping, Context},
+        disk_usage::file_size::FileSize,
+        tree::{node::Node, Tree},
+    },
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Formatter},
+};
+
+#[cfg(unix)]
+use crate::render::context::owner::Owner;
+
+use super::Breakdown;
+
+/// Label of the group of files without an extension.
+const NO_EXTENSION: &str = "(no extension)";
+
+/// How many extensions to list in the label of a group of files that share an icon.
+const MAX_EXTENSIONS: usize = 4;
+
+/// Disk usage of the files that fall into one group.
+#[derive(Default)]
+struct Group {
+    bytes: u64,
+    files: usize,
+    extensions: BTreeSet<String>,
+}
+
+impl Display for Tree<Breakdown> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let grouping = ctx.breakdown.unwrap_or_default();
+
+        #[cfg(unix)]
+        if !ctx.owners.is_empty() {
+            // Each table gets a heading only if there's more than one.
+            let headed = ctx.owners.len() > 1;
+
+            for (i, &owner) in ctx.owners.iter().enumerate() {
+                if i > 0 {
+                    writeln!(f)?;
+                }
+
+                if headed {
+                    writeln!(f, "{}", heading(owner))?;
+                }
+
+                let mut names = HashMap::new();
+
+                let groups = self.tally(|node| {
+                    let id = node.owner_id(owner)?;
+                    Some(names.entry(id).or_insert_with(|| owner.name_of(id)).clone())
+                });
+
+                write_table(f, groups, Grouping::Ext, ctx)?;
+            }
+
+            return Ok(());
+        }
+
+        let groups = self.tally(|node| match grouping {
+            Grouping::Ext => Some(extension(node)),
+            Grouping::Icon => {
+                Some(icons::compute(node.path(), node.file_type(), None).into_owned())
+            }
+        });
+
+        write_table(f, groups, grouping, ctx)
+    }
+}
+
+impl Tree<Breakdown> {
+    /// Sums up the disk usage of regular files grouped by whatever `key_of` maps them to. Files
+    /// that `key_of` maps to nothing are left out.
+    fn tally<F>(&self, mut key_of: F) -> HashMap<String, Group>
+    where
+        F: FnMut(&Node) -> Option<String>,
+    {
+        let arena = self.arena();
+        let ctx = self.context();
+
+        let mut groups: HashMap<String, Group> = HashMap::new();
+
+        for node_id in self.root_id().descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.is_dir() || node.is_symlink() || node.is_collapsed() {
+                continue;
+            }
+
+            let Some(key) = key_of(node) else {
+                continue;
+            };
+
+            let group = groups.entry(key).or_default();
+
+            group.bytes += node.charged_bytes(ctx.hardlinks);
+
+            group.files += 1;
+            group.extensions.insert(extension(node));
+        }
+
+        groups
+    }
+}
+
+/// Writes one line for each group, largest first.
+fn write_table(
+    f: &mut Formatter<'_>,
+    groups: HashMap<String, Group>,
+    grouping: Grouping,
+    ctx: &Context,
+) -> fmt::Result {
+    let total_bytes = groups.values().map(|group| group.bytes).sum::<u64>();
+
+    let mut groups = groups
+        .into_iter()
+        .map(|(key, group)| (label(&key, &group, grouping), group))
+        .collect::<Vec<_>>();
+
+    groups.sort_by(|(label_a, group_a), (label_b, group_b)| {
+        (Reverse(group_a.bytes), label_a).cmp(&(Reverse(group_b.bytes), label_b))
+    });
+
+    let sizes = groups
+        .iter()
+        .map(|(_, group)| file_size(group.bytes, ctx))
+        .collect::<Vec<_>>();
+
+    let size_width = sizes.iter().map(|fs| fs.size_columns).max().unwrap_or(0);
+
+    let files_width = groups
+        .iter()
+        .map(|(_, group)| group.files.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    for ((label, group), size) in groups.iter().zip(sizes) {
+        let percent = if total_bytes == 0 {
+            0.0
+        } else {
+            group.bytes as f64 / total_bytes as f64 * 100.0
+        };
+
+        writeln!(
+            f,
+            "{} {percent:>5.1}% {:>files_width$} {} {label}",
+            size.format(size_width, ctx.max_size_unit_width),
+            group.files,
+            if group.files == 1 { "file " } else { "files" },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Heading of the table of disk usage by user or by group when both are requested.
+#[cfg(unix)]
+const fn heading(owner: Owner) -> &'static str {
+    match owner {
+        Owner::User => "By user:",
+        Owner::Group => "By group:",
+    }
+}
+
+/// The lowercase extension of the [Node]'s file name if it has one.
+fn extension(node: &Node) -> String {
+    node.path().extension().map_or_else(
+        || String::from(NO_EXTENSION),
+        |ext| ext.to_string_lossy().to_lowercase(),
+    )
+}
+
+/// Label of the group at `key`. Groups of files that share an icon are labeled with the icon
+/// followed by the extensions of the files therein.
+fn label(key: &str, group: &Group, grouping: Grouping) -> String {
+    match grouping {
+        Grouping::Ext => String::from(key),
+        Grouping::Icon => {
+            let mut extensions = group
+                .extensions
+                .iter()
+                .take(MAX_EXTENSIONS)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+
+            if group.extensions.len() > MAX_EXTENSIONS {
+                extensions.push("\u{2026}");
+            }
+
+            format!("{key} {}", extensions.join(", "))
+        }
+    }
+}
+
+/// The [FileSize] of `bytes` of the [DiskUsage] in use.
+///
+/// [DiskUsage]: crate::render::disk_usage::file_size::DiskUsage
+fn file_size(bytes: u64, ctx: &Context) -> FileSize {
+    let mut file_size = FileSize::new(bytes, ctx.disk_usage, ctx.human, ctx.unit);
+    file_size.precompute_unpadded_display();
+    file_size
+}
@@ -0,0 +1,158 @@
+use crate::render::tree::{node::Node, Tree};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+#[cfg(unix)]
+use crate::render::context::{time::Stamp, Context};
+
+use super::{file_type, relative_path, Csv, TreeVariant, Tsv};
+
+/// Unit tests for the quoting of fields.
+#[cfg(test)]
+mod test;
+
+/// Records are terminated by CRLF as per RFC 4180.
+const RECORD_TERMINATOR: &str = "\r\n";
+
+impl Display for Tree<Csv> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_delimited(self, f, ',')
+    }
+}
+
+impl Display for Tree<Tsv> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_delimited(self, f, '\t')
+    }
+}
+
+/// Writes a header row followed by one record per [Node] in the same order as the [`Flat`] view
+/// with each field separated by `delimiter`.
+///
+/// [`Flat`]: super::Flat
+fn write_delimited<T: TreeVariant>(
+    tree: &Tree<T>,
+    f: &mut Formatter,
+    delimiter: char,
+) -> fmt::Result {
+    let arena = tree.arena();
+    let root_id = tree.root_id();
+    let ctx = tree.context();
+    let max_depth = ctx.level();
+    let root_path = ctx.dir_canonical();
+
+    #[allow(unused_mut)]
+    let mut header = vec!["path", "depth", "type", "bytes", "human_size"];
+
+    #[cfg(unix)]
+    if ctx.long {
+        header.extend(["ino", "nlink", "blocks", "permissions", "octal"]);
+        header.push(stamp_column(ctx));
+    }
+
+    write_record(f, header.into_iter().map(Cow::from), delimiter)?;
+
+    for node_id in root_id.descendants(arena) {
+        let node = arena[node_id].get();
+
+        if node.depth() > max_depth {
+            continue;
+        }
+
+        let size = node.file_size();
+
+        #[allow(unused_mut)]
+        let mut fields = vec![
+            Cow::from(relative_path(node, &root_path)),
+            Cow::from(node.depth().to_string()),
+            Cow::from(file_type(node)),
+            size.map_or_else(Cow::default, |fs| Cow::from(fs.bytes.to_string())),
+            size.map_or_else(Cow::default, |fs| Cow::from(fs.human_readable_display())),
+        ];
+
+        #[cfg(unix)]
+        if ctx.long {
+            fields.extend(long_fields(node, ctx));
+        }
+
+        write_record(f, fields.into_iter(), delimiter)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single record, quoting fields where necessary.
+fn write_record<'a>(
+    f: &mut Formatter,
+    fields: impl Iterator<Item = Cow<'a, str>>,
+    delimiter: char,
+) -> fmt::Result {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            write!(f, "{delimiter}")?;
+        }
+        write!(f, "{}", quote(&field, delimiter))?;
+    }
+
+    write!(f, "{RECORD_TERMINATOR}")
+}
+
+/// Fields containing the delimiter, double-quotes, or line breaks are enclosed in double-quotes
+/// and any double-quotes within are escaped by preceding them with another double-quote as per
+/// RFC 4180.
+fn quote(field: &str, delimiter: char) -> Cow<'_, str> {
+    let needs_quotes = field
+        .chars()
+        .any(|ch| ch == delimiter || ch == '"' || ch == '\r' || ch == '\n');
+
+    if needs_quotes {
+        Cow::from(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::from(field)
+    }
+}
+
+/// Name of the column for the timestamp chosen via `--time`.
+#[cfg(unix)]
+const fn stamp_column(ctx: &Context) -> &'static str {
+    match ctx.time {
+        Some(Stamp::Created) => "created",
+        Some(Stamp::Accessed) => "accessed",
+        Some(Stamp::Modified) | None => "modified",
+    }
+}
+
+/// Fields that are only included with `--long`.
+#[cfg(unix)]
+fn long_fields(node: &Node, ctx: &Context) -> Vec<Cow<'static, str>> {
+    use chrono::{offset::Local, DateTime};
+
+    let num = |n: Option<u64>| n.map_or_else(Cow::default, |n| Cow::from(n.to_string()));
+
+    let mode = node.mode();
+
+    let datetime = match ctx.time() {
+        Stamp::Created => node.created(),
+        Stamp::Accessed => node.accessed(),
+        Stamp::Modified => node.modified(),
+    };
+
+    let timestamp = datetime
+        .map(DateTime::<Local>::from)
+        .map_or_else(Cow::default, |dt| {
+            Cow::from(dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        });
+
+    vec![
+        num(node.ino()),
+        num(node.nlink()),
+        num(node.blocks()),
+        mode.as_ref()
+            .map_or_else(Cow::default, |m| Cow::from(m.to_string())),
+        mode.as_ref()
+            .map_or_else(Cow::default, |m| Cow::from(format!("{m:04o}"))),
+        timestamp,
+    ]
+}
@@ -0,0 +1,10 @@
+use super::quote;
+
+#[test]
+fn rfc_4180_quoting() {
+    assert_eq!(quote("plain.txt", ','), "plain.txt");
+    assert_eq!(quote("a,b.txt", ','), "\"a,b.txt\"");
+    assert_eq!(quote("a,b.txt", '\t'), "a,b.txt");
+    assert_eq!(quote("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+    assert_eq!(quote("line\nbreak", '\t'), "\"line\nbreak\"");
+}
@@ -0,0 +1,97 @@
+use crate::render::tree::{node::Node, Tree};
+use indextree::NodeId;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use super::Dot;
+
+/// Unit tests for the escaping of labels.
+#[cfg(test)]
+mod test;
+
+/// Hue of the fill color in the HSV space that Graphviz understands; saturation then scales with
+/// size so that larger entries stand out.
+const HUE: f64 = 0.0;
+
+/// Upper bound for the width of a node's outline.
+const MAX_PENWIDTH: f64 = 5.0;
+
+impl Display for Tree<Dot> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let max_depth = self.context().level();
+
+        let root_bytes = arena[root_id].get().file_size().map_or(0, |fs| fs.bytes);
+
+        writeln!(f, "digraph erdtree {{")?;
+        writeln!(f, "    rankdir=LR;")?;
+        writeln!(
+            f,
+            r#"    node [style="rounded,filled", fontname="Helvetica"];"#
+        )?;
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            writeln!(f, "    {};", dot_node(node_id, node, root_bytes))?;
+
+            if let Some(parent_id) = arena[node_id].parent() {
+                writeln!(f, "    {} -> {};", id(parent_id), id(node_id))?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// Statement declaring a single [Node] with its label and size-dependent attributes.
+#[allow(clippy::cast_precision_loss)]
+fn dot_node(node_id: NodeId, node: &Node, root_bytes: u64) -> String {
+    let name = node.file_name().to_string_lossy();
+
+    let (label, ratio) = node.file_size().map_or_else(
+        || (escape(&name).into_owned(), 0.0),
+        |fs| {
+            let label = format!("{}\\n{}", escape(&name), fs.human_readable_display());
+            let ratio = if root_bytes == 0 {
+                0.0
+            } else {
+                fs.bytes as f64 / root_bytes as f64
+            };
+            (label, ratio)
+        },
+    );
+
+    let shape = if node.is_dir() { "folder" } else { "note" };
+
+    // Square root so that entries that are a small fraction of the root still get some color.
+    let saturation = ratio.sqrt().clamp(0.0, 1.0);
+    let penwidth = (MAX_PENWIDTH - 1.0).mul_add(ratio, 1.0);
+
+    format!(
+        r#"{} [label="{label}", shape={shape}, fillcolor="{HUE:.3} {saturation:.3} 1.000", penwidth={penwidth:.2}]"#,
+        id(node_id)
+    )
+}
+
+/// Graphviz identifier of the node at `node_id`.
+fn id(node_id: NodeId) -> String {
+    let index: usize = node_id.into();
+    format!("n{index}")
+}
+
+/// Escapes characters that are significant within quoted Graphviz strings.
+fn escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['"', '\\']) {
+        return Cow::from(text);
+    }
+
+    Cow::from(text.replace('\\', "\\\\").replace('"', "\\\""))
+}
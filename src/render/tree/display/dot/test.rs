@@ -0,0 +1,7 @@
+use super::escape;
+
+#[test]
+fn dot_escape() {
+    assert_eq!(escape("lipsum.txt"), "lipsum.txt");
+    assert_eq!(escape(r#"a"b\c"#), r#"a\"b\\c"#);
+}
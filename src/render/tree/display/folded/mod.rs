@@ -0,0 +1,67 @@
+use crate::render::tree::{node::Node, Tree};
+use indextree::{Arena, NodeId};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use super::Folded;
+
+/// Unit tests for the frames of stacks.
+#[cfg(test)]
+mod test;
+
+impl Display for Tree<Folded> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let max_depth = self.context().level();
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+
+            // Directories at the boundary of `--level` stand in for everything beneath them.
+            let is_leaf = !node.is_dir() || node.depth() == max_depth;
+
+            if !is_leaf || node.depth() > max_depth {
+                continue;
+            }
+
+            let Some(file_size) = node.file_size() else {
+                continue;
+            };
+
+            let bytes = node.charged_bytes(self.context().hardlinks);
+
+            if bytes == 0 && file_size.bytes > 0 {
+                continue;
+            }
+
+            writeln!(f, "{} {bytes}", stack(node_id, arena))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Semicolon-separated names of all of the ancestors of the [Node] at `node_id`, root first,
+/// followed by its own name.
+fn stack(node_id: NodeId, arena: &Arena<Node>) -> String {
+    let mut frames = node_id
+        .ancestors(arena)
+        .map(|id| frame(arena[id].get().file_name().to_string_lossy()))
+        .collect::<Vec<_>>();
+
+    frames.reverse();
+
+    frames.join(";")
+}
+
+/// The `name` of a [Node] with characters that are significant to the folded format replaced.
+fn frame(name: Cow<'_, str>) -> Cow<'_, str> {
+    if name.contains([';', '\n', '\r']) {
+        Cow::from(name.replace([';', '\n', '\r'], "_"))
+    } else {
+        name
+    }
+}
@@ -0,0 +1,8 @@
+use super::frame;
+use std::borrow::Cow;
+
+#[test]
+fn folded_frame() {
+    assert_eq!(frame(Cow::from("lipsum.txt")), "lipsum.txt");
+    assert_eq!(frame(Cow::from("a;b\nc")), "a_b_c");
+}
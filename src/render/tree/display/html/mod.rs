@@ -0,0 +1,235 @@
+use crate::render::{
+    context::Context,
+    disk_usage::file_size::FileSize,
+    tree::{node::Node, Tree},
+};
+use indextree::{Arena, NodeId};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{file_type, Html};
+
+/// Unit tests for the escaping of text.
+#[cfg(test)]
+mod test;
+
+/// Inlined stylesheet so that the report is self-contained.
+const STYLE: &str = r"
+body { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 14px; margin: 2em; color: #222; }
+header { margin-bottom: 1em; }
+header input, header select { font: inherit; margin-right: 1em; }
+ul { list-style: none; margin: 0; padding-left: 1.5em; }
+ul.root { padding-left: 0; }
+summary { cursor: pointer; }
+.entry { display: inline-flex; gap: 1em; }
+.size { display: inline-block; min-width: 7em; text-align: right; color: #2a7; }
+.pct { display: inline-block; min-width: 4.5em; text-align: right; color: #a52; }
+.count { color: #888; }
+.directory > details > summary .name { font-weight: bold; color: #36c; }
+.symlink .name { color: #c33; }
+.hidden { display: none; }
+";
+
+/// Inlined script providing client-side search and sorting.
+const SCRIPT: &str = r#"
+const items = () => Array.from(document.querySelectorAll("li[data-name]"));
+
+function search(query) {
+  query = query.toLowerCase();
+  for (const li of items()) li.classList.toggle("hidden", query !== "");
+  if (query === "") return;
+  for (const li of items()) {
+    if (!li.dataset.name.toLowerCase().includes(query)) continue;
+    for (let el = li; el && el.tagName !== "BODY"; el = el.parentElement) {
+      if (el.tagName === "LI") el.classList.remove("hidden");
+      if (el.tagName === "DETAILS") el.open = true;
+    }
+    for (const child of li.querySelectorAll("li[data-name]")) child.classList.remove("hidden");
+  }
+}
+
+function sort(key) {
+  const dirsFirst = document.getElementById("dirs-first").checked;
+  const cmp = (a, b) => {
+    if (dirsFirst) {
+      const dirs = (b.classList.contains("directory") ? 1 : 0) - (a.classList.contains("directory") ? 1 : 0);
+      if (dirs !== 0) return dirs;
+    }
+    const sa = Number(a.dataset.size), sb = Number(b.dataset.size);
+    switch (key) {
+      case "size": return sb - sa;
+      case "size-rev": return sa - sb;
+      case "count": return Number(b.dataset.count) - Number(a.dataset.count);
+      case "count-rev": return Number(a.dataset.count) - Number(b.dataset.count);
+      default: return a.dataset.name.localeCompare(b.dataset.name);
+    }
+  };
+  for (const ul of document.querySelectorAll("ul")) {
+    const children = Array.from(ul.children).sort(cmp);
+    for (const child of children) ul.appendChild(child);
+  }
+}
+
+document.getElementById("search").addEventListener("input", (e) => search(e.target.value));
+document.getElementById("sort").addEventListener("change", (e) => sort(e.target.value));
+document.getElementById("dirs-first").addEventListener("change", () => sort(document.getElementById("sort").value));
+"#;
+
+impl Display for Tree<Html> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let ctx = self.context();
+        let title = escape(&arena[root_id].get().path().to_string_lossy()).into_owned();
+
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html lang=\"en\">")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>erdtree: {title}</title>")?;
+        writeln!(f, "<style>{STYLE}</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<header>")?;
+        writeln!(f, "<h1>{title}</h1>")?;
+        writeln!(
+            f,
+            "<input id=\"search\" type=\"search\" placeholder=\"Search\">"
+        )?;
+        writeln!(f, "<select id=\"sort\">")?;
+
+        for (value, label) in [
+            ("name", "Name"),
+            ("size", "Size, largest first"),
+            ("size-rev", "Size, smallest first"),
+            ("count", "Descendants, most first"),
+            ("count-rev", "Descendants, fewest first"),
+        ] {
+            let selected = if value == sort_key(ctx) {
+                " selected"
+            } else {
+                ""
+            };
+            writeln!(f, "<option value=\"{value}\"{selected}>{label}</option>")?;
+        }
+
+        writeln!(f, "</select>")?;
+
+        let checked = if ctx.dirs_first { " checked" } else { "" };
+        writeln!(
+            f,
+            "<label><input id=\"dirs-first\" type=\"checkbox\"{checked}> Directories first</label>"
+        )?;
+
+        writeln!(f, "</header>")?;
+        writeln!(f, "<ul class=\"root\">")?;
+        write_node(f, root_id, arena, ctx)?;
+        writeln!(f, "</ul>")?;
+        writeln!(f, "<script>{SCRIPT}</script>")?;
+        writeln!(f, "</body>")?;
+        write!(f, "</html>")
+    }
+}
+
+/// Recursively writes a list item for the [Node] at `node_id` and all of its descendants up to
+/// `--level`. Directories are rendered as collapsible elements.
+fn write_node(
+    f: &mut Formatter,
+    node_id: NodeId,
+    arena: &Arena<Node>,
+    ctx: &Context,
+) -> fmt::Result {
+    let node = arena[node_id].get();
+    let name = node.file_name().to_string_lossy();
+    let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+    let size = node
+        .file_size()
+        .map_or_else(String::new, FileSize::human_readable_display);
+
+    let parent_bytes = node_id
+        .ancestors(arena)
+        .nth(1)
+        .and_then(|parent_id| arena[parent_id].get().file_size())
+        .map_or(bytes, |fs| fs.bytes);
+
+    let pct = if parent_bytes == 0 {
+        0.0
+    } else {
+        bytes as f64 / parent_bytes as f64 * 100.0
+    };
+
+    write!(
+        f,
+        "<li class=\"{}\" data-name=\"{}\" data-size=\"{bytes}\" data-count=\"{}\">",
+        file_type(node),
+        escape(&name),
+        node.descendants().total()
+    )?;
+
+    let entry = format!(
+        "<span class=\"entry\"><span class=\"size\">{size}</span><span class=\"pct\">{pct:.1}%</span><span class=\"name\">{}</span>",
+        escape(&name)
+    );
+
+    if node.is_dir() {
+        let count = Tree::<Html>::compute_file_count(node_id, arena);
+        let open = if node.depth() == 0 { " open" } else { "" };
+
+        writeln!(
+            f,
+            "<details{open}><summary>{entry}<span class=\"count\">{count}</span></span></summary>"
+        )?;
+
+        if node.depth() < ctx.level() {
+            writeln!(f, "<ul>")?;
+
+            for child_id in node_id.children(arena) {
+                write_node(f, child_id, arena, ctx)?;
+            }
+
+            writeln!(f, "</ul>")?;
+        }
+
+        writeln!(f, "</details></li>")
+    } else {
+        writeln!(f, "{entry}</span></li>")
+    }
+}
+
+/// The sort-key used by the report's client-side sorting that corresponds to `--sort`.
+const fn sort_key(ctx: &Context) -> &'static str {
+    use crate::render::context::sort::SortType;
+
+    match ctx.sort {
+        SortType::Name => "name",
+        SortType::Size => "size",
+        SortType::SizeRev => "size-rev",
+        SortType::Count => "count",
+        SortType::CountRev => "count-rev",
+    }
+}
+
+/// Escapes characters that have special meaning in HTML. Also suitable for XML.
+pub(super) fn escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::from(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    Cow::from(escaped)
+}
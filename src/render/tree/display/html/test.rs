@@ -0,0 +1,7 @@
+use super::escape;
+
+#[test]
+fn html_escape() {
+    assert_eq!(escape("lipsum.txt"), "lipsum.txt");
+    assert_eq!(escape("<a & 'b'>\""), "&lt;a &amp; &#39;b&#39;&gt;&quot;");
+}
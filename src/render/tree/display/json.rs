@@ -0,0 +1,111 @@
+use crate::render::{
+    context::Context,
+    tree::{count::FileCount, node::Node, Tree},
+};
+use indextree::{Arena, NodeId};
+use serde::Serialize;
+use std::{
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
+
+#[cfg(unix)]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{file_type, relative_path, Json};
+
+/// Serializable representation of a single [Node] and all of its descendants.
+#[derive(Serialize)]
+struct JsonNode {
+    name: String,
+    path: String,
+    file_type: &'static str,
+    size: Option<u64>,
+    count: FileCount,
+
+    #[cfg(unix)]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    long: Option<JsonLongAttrs>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Self>,
+}
+
+/// Attributes that are only included in the output when `--long` is used.
+#[cfg(unix)]
+#[derive(Serialize)]
+struct JsonLongAttrs {
+    ino: Option<u64>,
+    nlink: Option<u64>,
+    blocks: Option<u64>,
+    mode: Option<String>,
+    permissions: Option<String>,
+    modified: Option<u64>,
+    created: Option<u64>,
+    accessed: Option<u64>,
+}
+
+impl Display for Tree<Json> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let root_path = arena[root_id].get().path();
+
+        let root = JsonNode::new(root_id, arena, root_path, self.context());
+
+        let out = serde_json::to_string_pretty(&root).map_err(|_| fmt::Error)?;
+
+        write!(f, "{out}")
+    }
+}
+
+impl JsonNode {
+    /// Recursively builds a [JsonNode] for the [Node] at `node_id`, stopping at `--level`.
+    fn new(node_id: NodeId, arena: &Arena<Node>, root_path: &Path, ctx: &Context) -> Self {
+        let node = arena[node_id].get();
+
+        let children = if node.depth() < ctx.level() {
+            node_id
+                .children(arena)
+                .map(|child_id| Self::new(child_id, arena, root_path, ctx))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Self {
+            name: node.file_name().to_string_lossy().into_owned(),
+            path: relative_path(node, root_path),
+            file_type: file_type(node),
+            size: node.file_size().map(|fs| fs.bytes),
+            count: Tree::<Json>::compute_file_count(node_id, arena),
+            #[cfg(unix)]
+            long: ctx.long.then(|| JsonLongAttrs::from(node)),
+            children,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<&Node> for JsonLongAttrs {
+    fn from(node: &Node) -> Self {
+        let mode = node.mode();
+
+        Self {
+            ino: node.ino(),
+            nlink: node.nlink(),
+            blocks: node.blocks(),
+            mode: mode.as_ref().map(|m| format!("{m:04o}")),
+            permissions: mode.as_ref().map(ToString::to_string),
+            modified: node.modified().and_then(epoch_secs),
+            created: node.created().and_then(epoch_secs),
+            accessed: node.accessed().and_then(epoch_secs),
+        }
+    }
+}
+
+/// Seconds since the Unix epoch.
+#[cfg(unix)]
+fn epoch_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
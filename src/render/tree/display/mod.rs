@@ -3,7 +3,10 @@ use crate::render::{
     tree::{count::FileCount, node::Node, Tree},
 };
 use indextree::{NodeEdge, NodeId};
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
 
 /// Empty trait used to constrain generic parameter `display_variant` of [Tree].
 pub trait TreeVariant {}
@@ -17,9 +20,83 @@ pub struct Inverted {}
 /// For generating plain-text report of disk usage without ASCII tree.
 pub struct Flat {}
 
+/// For generating a nested JSON document of the entire tree.
+pub struct Json {}
+
+/// For generating newline-delimited JSON with one record per node as traversal proceeds.
+pub struct Ndjson {}
+
+/// For generating comma-separated values with one record per node.
+pub struct Csv {}
+
+/// For generating tab-separated values with one record per node.
+pub struct Tsv {}
+
+/// For generating a self-contained, interactive HTML report.
+pub struct Html {}
+
+/// For generating an export that can be browsed with ncdu.
+pub struct Ncdu {}
+
+/// For generating a Graphviz DOT graph of the directory hierarchy.
+pub struct Dot {}
+
+/// For generating a squarified treemap of disk usage as SVG.
+pub struct Svg {}
+
+/// For generating folded stacks that flame graph tools consume.
+pub struct Folded {}
+
+/// For generating a ranking of the largest files or directories.
+pub struct Top {}
+
+/// For generating a summary of the disk usage of files grouped by extension or icon.
+pub struct Breakdown {}
+
 impl TreeVariant for Regular {}
 impl TreeVariant for Flat {}
 impl TreeVariant for Inverted {}
+impl TreeVariant for Json {}
+impl TreeVariant for Ndjson {}
+impl TreeVariant for Csv {}
+impl TreeVariant for Tsv {}
+impl TreeVariant for Html {}
+impl TreeVariant for Ncdu {}
+impl TreeVariant for Dot {}
+impl TreeVariant for Svg {}
+impl TreeVariant for Folded {}
+impl TreeVariant for Top {}
+impl TreeVariant for Breakdown {}
+
+/// Summary of the disk usage of the files of [Tree] by group.
+mod breakdown;
+
+/// Serialization of [Tree] into delimited text such as CSV and TSV.
+mod delimited;
+
+/// Graphviz DOT graph of [Tree].
+mod dot;
+
+/// Folded stacks of [Tree] for use with flame graph tools.
+mod folded;
+
+/// Interactive HTML report of [Tree].
+mod html;
+
+/// Serialization of [Tree] into a nested JSON document.
+mod json;
+
+/// Serialization of [Tree] into the ncdu JSON export format.
+mod ncdu;
+
+/// Newline-delimited JSON records of [Tree], written as traversal proceeds where possible.
+mod ndjson;
+
+/// Treemap of [Tree] rendered as SVG.
+mod svg;
+
+/// Ranking of the largest entries of [Tree].
+mod top;
 
 /// Utilities to pick the appropriate theme to paint box drawing characters.
 mod theme;
@@ -228,3 +305,23 @@ impl Display for Tree<Flat> {
         Ok(())
     }
 }
+
+/// Path of [Node] relative to the root directory. The root itself is represented as `.`.
+fn relative_path(node: &Node, root_path: &Path) -> String {
+    match node.path().strip_prefix(root_path) {
+        Ok(path) if path.as_os_str().is_empty() => String::from("."),
+        Ok(path) => path.to_string_lossy().into_owned(),
+        Err(_) => node.path().to_string_lossy().into_owned(),
+    }
+}
+
+/// Stable, lowercase name of the [Node]'s file-type.
+const fn file_type(node: &Node) -> &'static str {
+    if node.is_dir() {
+        "directory"
+    } else if node.is_symlink() {
+        "symlink"
+    } else {
+        "file"
+    }
+}
@@ -0,0 +1,120 @@
+use crate::render::tree::{node::Node, Tree};
+use indextree::{Arena, NodeId};
+use serde_json::{json, Map, Value};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::Ncdu;
+
+/// Major version of the ncdu export format.
+const MAJOR_VERSION: u64 = 1;
+
+/// Minor version of the ncdu export format.
+const MINOR_VERSION: u64 = 2;
+
+impl Display for Tree<Ncdu> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let root_name = self.context().dir_canonical();
+
+        let metadata = json!({
+            "progname": env!("CARGO_PKG_NAME"),
+            "progver": env!("CARGO_PKG_VERSION"),
+            "timestamp": epoch_secs(SystemTime::now()).unwrap_or(0),
+        });
+
+        let root = entry(root_id, arena, &root_name.to_string_lossy(), None);
+
+        let export = Value::Array(vec![
+            Value::from(MAJOR_VERSION),
+            Value::from(MINOR_VERSION),
+            metadata,
+            root,
+        ]);
+
+        let out = serde_json::to_string(&export).map_err(|_| fmt::Error)?;
+
+        write!(f, "{out}")
+    }
+}
+
+/// Recursively builds the ncdu representation of the [Node] at `node_id`. Directories are arrays
+/// whose first element is the info block of the directory itself followed by their children;
+/// everything else is just an info block. `--level` is ignored given that ncdu needs the whole
+/// tree to compute sizes.
+fn entry(node_id: NodeId, arena: &Arena<Node>, name: &str, parent_dev: Option<u64>) -> Value {
+    let node = arena[node_id].get();
+    let info = info(node, name, parent_dev);
+
+    if !node.is_dir() {
+        return Value::Object(info);
+    }
+
+    let dev = node.inode().map(|inode| inode.dev);
+
+    let mut items = vec![Value::Object(info)];
+
+    for child_id in node_id.children(arena) {
+        let child_name = arena[child_id].get().file_name().to_string_lossy();
+        items.push(entry(child_id, arena, &child_name, dev));
+    }
+
+    Value::Array(items)
+}
+
+/// The info block describing a single [Node]. `dev` is only recorded for the root and whenever
+/// it differs from that of the parent directory.
+fn info(node: &Node, name: &str, parent_dev: Option<u64>) -> Map<String, Value> {
+    let mut info = Map::new();
+
+    info.insert("name".into(), name.into());
+    info.insert("asize".into(), node.apparent_size().into());
+
+    #[cfg(unix)]
+    info.insert("dsize".into(), (node.blocks().unwrap_or(0) * 512).into());
+
+    #[cfg(not(unix))]
+    info.insert("dsize".into(), node.apparent_size().into());
+
+    if let Some(inode) = node.inode() {
+        if parent_dev != Some(inode.dev) {
+            info.insert("dev".into(), inode.dev.into());
+        }
+
+        info.insert("ino".into(), inode.ino.into());
+
+        if !node.is_dir() && inode.nlink > 1 {
+            info.insert("hlnkc".into(), true.into());
+            info.insert("nlink".into(), inode.nlink.into());
+        }
+    }
+
+    if !node.is_dir() && (node.is_symlink() || node.file_type().is_none()) {
+        info.insert("notreg".into(), true.into());
+    }
+
+    #[cfg(unix)]
+    if let Some(mode) = node.st_mode() {
+        info.insert("mode".into(), mode.into());
+    }
+
+    #[cfg(unix)]
+    if let Some((uid, gid)) = node.ids() {
+        info.insert("uid".into(), uid.into());
+        info.insert("gid".into(), gid.into());
+    }
+
+    if let Some(mtime) = node.modified().and_then(epoch_secs) {
+        info.insert("mtime".into(), mtime.into());
+    }
+
+    info
+}
+
+/// Seconds since the Unix epoch.
+fn epoch_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
@@ -0,0 +1,389 @@
+use crate::{
+    fs::inode::Inode,
+    render::{
+        context::{hardlink::Accounting, Context},
+        disk_usage::file_size::FileSize,
+        tree::{
+            error::Error,
+            interrupt::Outstanding,
+            node::{HardLink, Node},
+            visitor::TraversalState,
+            Result, Tree,
+        },
+    },
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    io::{Error as IoError, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{file_type, relative_path, Ndjson};
+
+/// Serializable representation of a single [Node] without its descendants.
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    path: &'a str,
+    depth: usize,
+    file_type: &'static str,
+    size: Option<u64>,
+
+    /// Whether the entry is a directory that wasn't fully read as traversal was interrupted, in
+    /// which case its size is partial.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    incomplete: bool,
+}
+
+/// Keeps track of the directories whose records can't be written until everything beneath them
+/// has been received, see [Tree::<Ndjson>::stream].
+struct Stream<'a, W> {
+    ctx: &'a Context,
+    out: W,
+    root_path: PathBuf,
+
+    /// Directories that aren't complete yet by path.
+    open: HashMap<PathBuf, OpenDir>,
+
+    /// The paths and sizes of the links received so far of files with multiple hard links that
+    /// are yet to be charged to their directories, see [HardLink].
+    links: HashMap<Inode, Vec<(PathBuf, u64)>>,
+
+    /// Whether the root directory was received.
+    has_root: bool,
+}
+
+/// A directory that was received but not everything beneath it.
+struct OpenDir {
+    node: Node,
+
+    /// The number of entries that were read from the directory, once known.
+    listed: Option<usize>,
+
+    /// The number of entries of the directory that were received.
+    received: usize,
+
+    /// Entries of the directory that were received but aren't charged to it yet, which are
+    /// directories that aren't complete and hard links whose charge isn't known.
+    uncharged: usize,
+
+    /// The aggregate size of the directory so far.
+    bytes: u64,
+
+    /// Whether anything beneath the directory survives pruning.
+    is_occupied: bool,
+}
+
+impl Display for Tree<Ndjson> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let ctx = self.context();
+        let max_depth = ctx.level();
+        let root_path = ctx.dir_canonical();
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            let out = record(node, &root_path).map_err(|_| fmt::Error)?;
+
+            writeln!(f, "{out}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Tree<Ndjson> {
+    /// Whether records can be written as traversal proceeds, which isn't the case if the [Tree]
+    /// isn't read from the filesystem or has to be assembled in full before anything is known
+    /// to be shown.
+    pub fn can_stream(ctx: &Context) -> bool {
+        ctx.import_ncdu.is_none()
+            && ctx.load.is_none()
+            && ctx.save.is_none()
+            && ctx.collapse.is_none()
+            && ctx.min_size().is_none()
+            && ctx.max_size().is_none()
+    }
+
+    /// Traverses the filesystem and writes the record of every file to `out` as soon as it's
+    /// received and that of every directory as soon as everything beneath it has been, rather
+    /// than once the whole [Tree] is assembled. Directories that are still incomplete when
+    /// traversal ends, which is only the case if it's interrupted, are written last and marked
+    /// as such. `out` is flushed whenever a directory is complete.
+    pub fn stream<W: Write>(ctx: &Context, out: W) -> Result<()> {
+        let res = Self::walk(ctx, &Outstanding::default(), true, |rx| {
+            let mut stream = Stream::new(ctx, out);
+
+            loop {
+                match rx.recv() {
+                    Ok(TraversalState::Ongoing(node)) => stream.receive(node)?,
+                    Ok(TraversalState::Listed(dir, count)) => stream.list(&dir, count)?,
+                    Ok(TraversalState::Unreadable(path)) => stream.skip(&path)?,
+                    Ok(TraversalState::Done) | Err(_) => break,
+                }
+            }
+
+            stream.finish()
+        });
+
+        match res {
+            // Consumers such as `head` may well stop reading early.
+            Err(Error::Write(e)) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+            res => res,
+        }
+    }
+}
+
+impl<'a, W: Write> Stream<'a, W> {
+    fn new(ctx: &'a Context, out: W) -> Self {
+        Self {
+            ctx,
+            out,
+            root_path: ctx.dir_canonical(),
+            open: HashMap::new(),
+            links: HashMap::new(),
+            has_root: false,
+        }
+    }
+
+    /// Writes the record of a file right away unless it's beyond `--level` or files are left
+    /// out altogether, whereas that of a directory is held back until it's complete.
+    fn receive(&mut self, node: Node) -> Result<()> {
+        let ctx = self.ctx;
+
+        if node.depth() == 0 {
+            self.has_root = true;
+            self.open
+                .insert(node.path().to_path_buf(), OpenDir::new(node));
+            return Ok(());
+        }
+
+        let parent_path = node
+            .parent_path()
+            .ok_or(Error::ExpectedParent)?
+            .to_path_buf();
+        let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+        let is_dir = node.is_dir();
+
+        // Which link is charged what isn't known until all of them are.
+        let link = node
+            .inode()
+            .filter(|_| node.is_hard_linked() && ctx.hardlinks != Accounting::Every);
+
+        let parent = self.open_dir(&parent_path)?;
+        parent.received += 1;
+
+        if is_dir || link.is_some() {
+            parent.uncharged += 1;
+        } else {
+            parent.bytes += bytes;
+        }
+
+        // Anything but a directory survives pruning.
+        parent.is_occupied |= !is_dir;
+
+        if is_dir {
+            self.open
+                .insert(node.path().to_path_buf(), OpenDir::new(node));
+            return Ok(());
+        }
+
+        if node.depth() <= ctx.level() && !ctx.dirs_only {
+            self.write(&node)?;
+        }
+
+        if let Some(inode) = link {
+            let links = self.links.entry(inode).or_default();
+            links.push((node.path().to_path_buf(), bytes));
+
+            if links.len() as u64 == inode.nlink {
+                self.charge_links(inode)?;
+            }
+        }
+
+        self.complete(parent_path)
+    }
+
+    /// Records the number of entries that were read from `dir`.
+    fn list(&mut self, dir: &Path, count: usize) -> Result<()> {
+        self.open_dir(dir)?.listed = Some(count);
+        self.complete(dir.to_path_buf())
+    }
+
+    /// Accounts for an entry that was read from its directory but never received.
+    fn skip(&mut self, path: &Path) -> Result<()> {
+        let parent_path = path.parent().ok_or(Error::ExpectedParent)?;
+
+        self.open_dir(parent_path)?.received += 1;
+        self.complete(parent_path.to_path_buf())
+    }
+
+    /// Charges every link of the file at `inode` received so far to its directory, ranked among
+    /// one another as in [Tree::rank_hard_links].
+    fn charge_links(&mut self, inode: Inode) -> Result<()> {
+        let Some(mut links) = self.links.remove(&inode) else {
+            return Ok(());
+        };
+
+        links.sort_by(|(path_a, _), (path_b, _)| path_a.cmp(path_b));
+
+        let accounting = self.ctx.hardlinks;
+        let count = links.len() as u64;
+
+        for (rank, (path, bytes)) in (0..).zip(links) {
+            let parent_path = path.parent().ok_or(Error::ExpectedParent)?.to_path_buf();
+            let parent = self.open_dir(&parent_path)?;
+
+            parent.bytes += HardLink { rank, count }.charge(bytes, accounting);
+            parent.uncharged -= 1;
+
+            self.complete(parent_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the record of the directory at `dir` if everything beneath it was received and
+    /// charges it to its parent, which may thereby become complete in turn.
+    fn complete(&mut self, mut dir: PathBuf) -> Result<()> {
+        while self.open.get(&dir).map_or(false, OpenDir::is_complete) {
+            match self.close(&dir)? {
+                Some(parent_path) => dir = parent_path,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the record of the directory at `dir` unless it's pruned and charges it to its
+    /// parent, the path of which is returned.
+    fn close(&mut self, dir: &Path) -> Result<Option<PathBuf>> {
+        let Some(OpenDir {
+            mut node,
+            bytes,
+            is_occupied,
+            ..
+        }) = self.open.remove(dir)
+        else {
+            return Ok(None);
+        };
+
+        let ctx = self.ctx;
+
+        let is_pruned = node.is_prunable(ctx, is_occupied);
+
+        if node.depth() == 0 && !is_occupied {
+            return Err(Error::NoMatches);
+        }
+
+        if bytes > 0 {
+            let mut file_size = FileSize::new(bytes, ctx.disk_usage, ctx.human, ctx.unit);
+            file_size.precompute_unpadded_display();
+            node.set_file_size(file_size);
+        }
+
+        if !is_pruned && node.depth() <= ctx.level() {
+            self.write(&node)?;
+        }
+
+        self.out.flush().map_err(Error::Write)?;
+
+        let Some(parent_path) = node.parent_path().filter(|_| node.depth() > 0) else {
+            return Ok(None);
+        };
+
+        let parent = self.open_dir(parent_path)?;
+        parent.bytes += bytes;
+        parent.uncharged -= 1;
+        parent.is_occupied |= !is_pruned;
+
+        Ok(Some(parent_path.to_path_buf()))
+    }
+
+    /// Charges the hard links whose other links were never received and writes the records of
+    /// whatever directories are left, deepest first, once traversal is over.
+    fn finish(mut self) -> Result<()> {
+        let inodes = self.links.keys().copied().collect::<Vec<_>>();
+
+        for inode in inodes {
+            self.charge_links(inode)?;
+        }
+
+        if !self.has_root {
+            return Err(Error::MissingRoot);
+        }
+
+        let mut dirs = self
+            .open
+            .iter_mut()
+            .map(|(path, dir)| {
+                dir.node.mark_incomplete();
+                (dir.node.depth(), path.clone())
+            })
+            .collect::<Vec<_>>();
+
+        dirs.sort_by(|(depth_a, _), (depth_b, _)| depth_b.cmp(depth_a));
+
+        for (_, dir) in dirs {
+            self.close(&dir)?;
+        }
+
+        self.out.flush().map_err(Error::Write)
+    }
+
+    /// Grabs the directory at `path` that is yet to be complete.
+    fn open_dir(&mut self, path: &Path) -> Result<&mut OpenDir> {
+        self.open.get_mut(path).ok_or(Error::ExpectedParent)
+    }
+
+    /// Writes the record of a single [Node].
+    fn write(&mut self, node: &Node) -> Result<()> {
+        let out = record(node, &self.root_path).map_err(|e| Error::Write(IoError::from(e)))?;
+
+        writeln!(self.out, "{out}").map_err(Error::Write)
+    }
+}
+
+impl OpenDir {
+    fn new(node: Node) -> Self {
+        // A directory's own size is only known if its own blocks are accounted for.
+        let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+        Self {
+            node,
+            listed: None,
+            received: 0,
+            uncharged: 0,
+            bytes,
+            is_occupied: false,
+        }
+    }
+
+    /// Whether everything that was read from the directory was received and charged to it.
+    fn is_complete(&self) -> bool {
+        self.listed == Some(self.received) && self.uncharged == 0
+    }
+}
+
+/// Serializes a single [Node] with its path relative to `root_path`.
+fn record(node: &Node, root_path: &Path) -> serde_json::Result<String> {
+    let path = relative_path(node, root_path);
+
+    let record = JsonRecord {
+        path: &path,
+        depth: node.depth(),
+        file_type: file_type(node),
+        size: node.file_size().map(|fs| fs.bytes),
+        incomplete: node.is_incomplete(),
+    };
+
+    serde_json::to_string(&record)
+}
@@ -0,0 +1,358 @@
+use crate::render::{
+    disk_usage::file_size::FileSize,
+    tree::{node::Node, Tree},
+};
+use ansi_term::{Color, Style};
+use indextree::{Arena, NodeId};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
+
+use super::{html::escape, Svg};
+
+/// Unit tests for the layout and coloring of the treemap.
+#[cfg(test)]
+mod test;
+
+/// Width of the rendered treemap in pixels.
+const WIDTH: f64 = 1280.0;
+
+/// Height of the rendered treemap in pixels.
+const HEIGHT: f64 = 800.0;
+
+/// Height reserved at the top of a directory for its label.
+const HEADER: f64 = 16.0;
+
+/// Gap between a directory's outline and its contents.
+const PADDING: f64 = 2.0;
+
+/// Horizontal space taken up by padding on either side of a directory's contents.
+const INSET: f64 = 2.0 * PADDING;
+
+/// Rough width of a single character of the label font used to decide whether a label fits.
+const CHAR_WIDTH: f64 = 6.5;
+
+/// Fill of directories.
+const DIR_FILL: &str = "#f2f2f2";
+
+/// Fill of files for which `LS_COLORS` doesn't specify a foreground.
+const DEFAULT_FILL: &str = "#b0b0b0";
+
+/// An axis-aligned rectangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl Display for Tree<Svg> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let root_path = arena[root_id].get().path();
+        let root_canonical = self.context().dir_canonical();
+
+        let treemap = Treemap {
+            arena,
+            root_path,
+            root_canonical: &root_canonical,
+            max_depth: self.context().level(),
+        };
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="11">"#
+        )?;
+
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            w: WIDTH,
+            h: HEIGHT,
+        };
+
+        treemap.write_node(f, root_id, rect)?;
+
+        write!(f, "</svg>")
+    }
+}
+
+/// State shared while recursively drawing the treemap.
+struct Treemap<'a> {
+    arena: &'a Arena<Node>,
+    root_path: &'a Path,
+    root_canonical: &'a Path,
+    max_depth: usize,
+}
+
+impl Treemap<'_> {
+    /// Draws the [Node] at `node_id` into `rect` and, if it's a directory within `--level`,
+    /// lays out its children within it.
+    fn write_node(&self, f: &mut Formatter<'_>, node_id: NodeId, rect: Rect) -> fmt::Result {
+        let node = self.arena[node_id].get();
+
+        let full_path = match node.path().strip_prefix(self.root_path) {
+            Ok(rel) if rel.as_os_str().is_empty() => self.root_canonical.to_path_buf(),
+            Ok(rel) => self.root_canonical.join(rel),
+            Err(_) => node.path().to_path_buf(),
+        };
+
+        let size = node
+            .file_size()
+            .map(FileSize::human_readable_display)
+            .unwrap_or_default();
+
+        let (fill, stroke) = if node.is_dir() {
+            (String::from(DIR_FILL), "#666")
+        } else {
+            (fill(node.style()), "#fff")
+        };
+
+        writeln!(f, "<g>")?;
+
+        writeln!(
+            f,
+            "<title>{}&#10;{size}</title>",
+            escape(&full_path.to_string_lossy())
+        )?;
+
+        writeln!(
+            f,
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{fill}" stroke="{stroke}" stroke-width="0.5"/>"#,
+            rect.x, rect.y, rect.w, rect.h
+        )?;
+
+        let name = node.file_name().to_string_lossy();
+
+        if let Some(label) = label(&name, rect.w - INSET) {
+            if rect.h >= HEADER {
+                writeln!(
+                    f,
+                    r#"<text x="{:.2}" y="{:.2}">{}</text>"#,
+                    rect.x + PADDING,
+                    rect.y + HEADER - 4.0,
+                    escape(&label)
+                )?;
+            }
+        }
+
+        writeln!(f, "</g>")?;
+
+        if node.is_dir() && node.depth() < self.max_depth {
+            let inner = Rect {
+                x: rect.x + PADDING,
+                y: rect.y + HEADER,
+                w: rect.w - INSET,
+                h: rect.h - HEADER - PADDING,
+            };
+
+            if inner.w >= 1.0 && inner.h >= 1.0 {
+                self.write_children(f, node_id, inner)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lays out the children of the directory at `node_id` that occupy any space, largest first.
+    #[allow(clippy::cast_precision_loss)]
+    fn write_children(&self, f: &mut Formatter<'_>, node_id: NodeId, rect: Rect) -> fmt::Result {
+        let mut children = node_id
+            .children(self.arena)
+            .filter_map(|id| {
+                self.arena[id]
+                    .get()
+                    .file_size()
+                    .filter(|fs| fs.bytes > 0)
+                    .map(|fs| (id, fs.bytes as f64))
+            })
+            .collect::<Vec<_>>();
+
+        children.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        let sizes = children.iter().map(|(_, size)| *size).collect::<Vec<_>>();
+
+        for ((child_id, _), child_rect) in children.iter().zip(squarify(&sizes, rect)) {
+            self.write_node(f, *child_id, child_rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Squarified treemap layout as described by Bruls, Huizing, and van Wijk. `sizes` must be
+/// sorted in descending order; the returned rectangles are in the same order and have areas
+/// proportional to their respective sizes.
+fn squarify(sizes: &[f64], rect: Rect) -> Vec<Rect> {
+    let total = sizes.iter().sum::<f64>();
+
+    if total <= 0.0 {
+        return vec![];
+    }
+
+    let scale = rect.w * rect.h / total;
+    let areas = sizes.iter().map(|s| s * scale).collect::<Vec<_>>();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut free = rect;
+    let mut start = 0;
+
+    while start < areas.len() {
+        let short_side = free.w.min(free.h);
+
+        let mut end = start + 1;
+        let mut ratio = worst_ratio(&areas[start..end], short_side);
+
+        while end < areas.len() {
+            let next = worst_ratio(&areas[start..=end], short_side);
+
+            if next > ratio {
+                break;
+            }
+
+            ratio = next;
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area = row.iter().sum::<f64>();
+
+        if free.w >= free.h {
+            let col_w = row_area / free.h;
+            let mut y = free.y;
+
+            for area in row {
+                let h = area / col_w;
+                rects.push(Rect {
+                    x: free.x,
+                    y,
+                    w: col_w,
+                    h,
+                });
+                y += h;
+            }
+
+            free.x += col_w;
+            free.w -= col_w;
+        } else {
+            let row_h = row_area / free.w;
+            let mut x = free.x;
+
+            for area in row {
+                let w = area / row_h;
+                rects.push(Rect {
+                    x,
+                    y: free.y,
+                    w,
+                    h: row_h,
+                });
+                x += w;
+            }
+
+            free.y += row_h;
+            free.h -= row_h;
+        }
+
+        start = end;
+    }
+
+    rects
+}
+
+/// The worst aspect ratio of the rectangles in `row` when laid out along `side`.
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum = row.iter().sum::<f64>();
+    let max = row.iter().copied().fold(f64::MIN, f64::max);
+    let min = row.iter().copied().fold(f64::MAX, f64::min);
+    let side_sq = side * side;
+    let sum_sq = sum * sum;
+
+    (side_sq * max / sum_sq).max(sum_sq / (side_sq * min))
+}
+
+/// Truncates `name` so that it fits within `width`; returns `None` if not even a single character
+/// would.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn label(name: &str, width: f64) -> Option<String> {
+    let max_chars = (width / CHAR_WIDTH).floor().max(0.0) as usize;
+
+    match name.chars().count() {
+        0 => None,
+        _ if max_chars == 0 => None,
+        n if n <= max_chars => Some(name.to_owned()),
+        _ if max_chars == 1 => None,
+        _ => {
+            let truncated = name.chars().take(max_chars - 1).collect::<String>();
+            Some(format!("{truncated}\u{2026}"))
+        }
+    }
+}
+
+/// CSS color of a file derived from the foreground of its `LS_COLORS` [Style].
+fn fill(style: Option<Style>) -> String {
+    let Some(color) = style.and_then(|s| s.foreground) else {
+        return String::from(DEFAULT_FILL);
+    };
+
+    let (r, g, b) = match color {
+        Color::Fixed(n) => fixed_rgb(n),
+        Color::RGB(r, g, b) => (r, g, b),
+        Color::Black => fixed_rgb(0),
+        Color::Red => fixed_rgb(1),
+        Color::Green => fixed_rgb(2),
+        Color::Yellow => fixed_rgb(3),
+        Color::Blue => fixed_rgb(4),
+        Color::Purple => fixed_rgb(5),
+        Color::Cyan => fixed_rgb(6),
+        Color::White => fixed_rgb(7),
+    };
+
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Maps an entry of the 256-color palette to RGB.
+#[allow(clippy::cast_possible_truncation)]
+const fn fixed_rgb(n: u8) -> (u8, u8, u8) {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+
+    const fn cube(level: u8) -> u8 {
+        if level == 0 {
+            0
+        } else {
+            55 + level * 40
+        }
+    }
+
+    match n {
+        0..=15 => BASIC[n as usize],
+        16..=231 => {
+            let i = n - 16;
+            (cube(i / 36), cube((i / 6) % 6), cube(i % 6))
+        }
+        _ => {
+            let gray = 8 + (n - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use super::{fill, squarify, Rect, DEFAULT_FILL};
+use ansi_term::Color;
+
+#[test]
+fn squarify_preserves_area() {
+    let rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        w: 600.0,
+        h: 400.0,
+    };
+
+    let sizes = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+    let rects = squarify(&sizes, rect);
+    let total = sizes.iter().sum::<f64>();
+
+    assert_eq!(rects.len(), sizes.len());
+
+    for (size, r) in sizes.iter().zip(&rects) {
+        let expected = size / total * rect.w * rect.h;
+        let area = r.w * r.h;
+        assert!((area - expected).abs() < 1e-6);
+        assert!(r.x >= 0.0 && r.y >= 0.0);
+        assert!(r.x + r.w <= rect.w + 1e-6 && r.y + r.h <= rect.h + 1e-6);
+    }
+}
+
+#[test]
+fn ls_colors_to_css() {
+    assert_eq!(fill(None), DEFAULT_FILL);
+    assert_eq!(fill(Some(Color::Fixed(196).normal())), "#ff0000");
+    assert_eq!(fill(Some(Color::RGB(1, 2, 3).normal())), "#010203");
+    assert_eq!(fill(Some(Color::Fixed(244).normal())), "#808080");
+}
@@ -0,0 +1,42 @@
+use crate::render::tree::Tree;
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display, Formatter},
+};
+
+use super::Top;
+
+impl Display for Tree<Top> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arena = self.arena();
+        let root_id = self.root_id();
+        let ctx = self.context();
+        let max_depth = ctx.level();
+
+        // The root is left out when ranking directories as it contains all of the others, as are
+        // the stand-ins for entries merged by `--collapse`.
+        let mut ranked = root_id
+            .descendants(arena)
+            .skip(1)
+            .map(|node_id| arena[node_id].get())
+            .filter(|node| node.is_dir() == ctx.top_dirs && !node.is_collapsed())
+            .filter(|node| node.depth() <= max_depth)
+            .collect::<Vec<_>>();
+
+        ranked.sort_by_key(|node| {
+            let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+            (Reverse(bytes), node.path())
+        });
+
+        ranked.truncate(ctx.top.unwrap_or(usize::MAX));
+
+        let rank_width = ranked.len().to_string().len();
+
+        for (i, node) in ranked.into_iter().enumerate() {
+            write!(f, "{:>rank_width$}. ", i + 1)?;
+            node.flat_display(f, ctx)?;
+        }
+
+        Ok(())
+    }
+}
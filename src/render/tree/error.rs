@@ -26,6 +26,9 @@ pub enum Error {
     #[error("Failed to compute root node.")]
     MissingRoot,
 
+    #[error("Failed to import ncdu export: {0}")]
+    NcduImport(String),
+
     #[error("No entries to show with given arguments.")]
     NoMatches,
 
@@ -36,6 +39,12 @@ pub enum Error {
     #[error("{0}")]
     Persmissions(#[from] PermissionsError),
 
+    #[error("Snapshot error: {0}")]
+    Snapshot(String),
+
     #[error("{0}")]
     UninitializedTheme(#[from] StyleError<'static>),
+
+    #[error("Failed to write output: {0}")]
+    Write(IoError),
 }
@@ -0,0 +1,144 @@
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Once,
+    },
+};
+
+/// Unit tests for the bookkeeping of directories that weren't fully read.
+#[cfg(test)]
+mod test;
+
+/// Exit code conventionally used by programs terminated by SIGINT.
+pub const EXIT_CODE: u8 = 130;
+
+/// Whether or not SIGINT was received during traversal.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether or not traversal is underway, during which SIGINT stops the traversal rather than the
+/// program.
+static TRAVERSING: AtomicBool = AtomicBool::new(false);
+
+/// Ensures the SIGINT handler is only installed once.
+static HANDLER: Once = Once::new();
+
+thread_local! {
+    /// Number of entries queued up for a visit by the walker on the current thread that are yet
+    /// to be attributed to the directory they were read from, see [Listings].
+    static QUEUED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Marks traversal as underway until dropped. The first SIGINT received in the meantime stops
+/// the traversal such that whatever was gathered up to that point can still be rendered; any
+/// further SIGINT, or one received outside of traversal, terminates the program as usual.
+pub struct TraversalGuard;
+
+impl TraversalGuard {
+    pub fn begin() -> Self {
+        HANDLER.call_once(|| {
+            let _ = ctrlc::set_handler(|| {
+                if !TRAVERSING.load(Ordering::SeqCst) || INTERRUPTED.swap(true, Ordering::SeqCst) {
+                    process::exit(i32::from(EXIT_CODE));
+                }
+            });
+        });
+
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        TRAVERSING.store(true, Ordering::SeqCst);
+
+        Self
+    }
+}
+
+impl Drop for TraversalGuard {
+    fn drop(&mut self) {
+        TRAVERSING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Was traversal interrupted by SIGINT?
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Records that an entry was queued up for a visit by the walker on the current thread.
+pub fn queue() {
+    QUEUED.with(|queued| queued.set(queued.get() + 1));
+}
+
+/// Keeps count of the entries of each directory that were queued up for a visit by the walker.
+/// Should traversal be interrupted, the directories with fewer entries received than queued up
+/// are the ones that weren't fully read. Directories are keyed by their raw path as hashing
+/// [Path] is comparatively slow given that it's done for every directory.
+#[derive(Clone, Default)]
+pub struct Outstanding(Arc<Mutex<HashMap<OsString, usize>>>);
+
+/// The counts of a single visitor thread that are merged into [Outstanding] once it's done so
+/// that the walker's threads don't contend over them. The walker reads a directory on the
+/// thread that visited it right after the visit, so whatever is queued up on a thread in between
+/// two visits belongs to the directory visited first.
+#[derive(Default)]
+pub struct Listings {
+    dir: Option<OsString>,
+    counts: HashMap<OsString, usize>,
+}
+
+impl Outstanding {
+    /// Adds the counts of a visitor thread that is done.
+    pub fn merge(&self, mut listings: Listings) {
+        listings.close();
+        self.0.lock().unwrap().extend(listings.counts);
+    }
+
+    /// The directories for which fewer entries were received, as reported by `received`, than
+    /// were queued up along with all of their ancestors, whose sizes are consequently partial as
+    /// well. Entries that couldn't be read at all count as not received.
+    pub fn incomplete<F>(&self, received: F) -> HashSet<PathBuf>
+    where
+        F: Fn(&Path) -> usize,
+    {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(path, count)| (Path::new(path), count))
+            .filter(|(path, count)| received(path) < **count)
+            .flat_map(|(path, _)| path.ancestors())
+            .map(Path::to_path_buf)
+            .collect()
+    }
+}
+
+impl Listings {
+    /// Marks `dir` as the directory that is read next on the current thread.
+    pub fn open(&mut self, dir: &Path) {
+        self.dir = Some(dir.as_os_str().to_owned());
+    }
+
+    /// Attributes the entries queued up on the current thread since the last visit to the
+    /// directory that was being read, if any, which is returned along with their number.
+    pub fn close(&mut self) -> Option<(PathBuf, usize)> {
+        let queued = QUEUED.with(|queued| queued.replace(0));
+
+        let dir = self.dir.take()?;
+        self.counts.insert(dir.clone(), queued);
+
+        Some((PathBuf::from(dir), queued))
+    }
+
+    /// Marks the directory that was being read as never complete as reading it was cut short.
+    /// Errors are reported both in the midst of reading a directory and before the next one, so
+    /// this errs on the side of marking a directory that was actually read in full.
+    pub fn truncate(&mut self) {
+        QUEUED.with(|queued| queued.set(0));
+
+        if let Some(dir) = self.dir.take() {
+            self.counts.insert(dir, usize::MAX);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{queue, Listings, Outstanding};
+use std::path::Path;
+
+#[test]
+fn outstanding_entries() {
+    let outstanding = Outstanding::default();
+    let mut listings = Listings::default();
+
+    listings.open(Path::new("/root"));
+    queue();
+    queue();
+
+    listings.close();
+    listings.open(Path::new("/root/a"));
+    queue();
+
+    listings.close();
+    listings.open(Path::new("/root/b"));
+    queue();
+
+    outstanding.merge(listings);
+
+    let received = |path: &Path| match path.to_str() {
+        Some("/root") => 2,
+        Some("/root/a") => 1,
+        _ => 0,
+    };
+
+    let incomplete = outstanding.incomplete(received);
+
+    assert!(incomplete.contains(Path::new("/root/b")));
+    assert!(incomplete.contains(Path::new("/root")));
+    assert!(!incomplete.contains(Path::new("/root/a")));
+}
+
+#[test]
+fn truncated_listing() {
+    let outstanding = Outstanding::default();
+    let mut listings = Listings::default();
+
+    // Entries queued up outside of any directory are not attributed to the next one.
+    queue();
+
+    listings.close();
+    listings.open(Path::new("/root"));
+    queue();
+    listings.truncate();
+
+    listings.open(Path::new("/root/a"));
+
+    outstanding.merge(listings);
+
+    let incomplete = outstanding.incomplete(|_| 1);
+
+    assert!(incomplete.contains(Path::new("/root")));
+    assert!(!incomplete.contains(Path::new("/root/a")));
+}
@@ -1,7 +1,7 @@
 use crate::{
     fs::inode::Inode,
     render::{
-        context::{file::FileType, output::ColumnProperties, Context},
+        context::{file::FileType, output::ColumnProperties, size::Cutoff, Context},
         disk_usage::file_size::FileSize,
         styles,
     },
@@ -11,37 +11,65 @@ use count::FileCount;
 use error::Error;
 use ignore::{WalkBuilder, WalkParallel};
 use indextree::{Arena, NodeId};
-use node::{cmp::NodeComparator, Node};
+use interrupt::{Outstanding, TraversalGuard};
+use node::{cmp::NodeComparator, HardLink, Node, Share};
+use progress::Progress;
 use std::{
     collections::{HashMap, HashSet},
-    convert::TryFrom,
     fs,
     marker::PhantomData,
-    path::PathBuf,
+    path::{Path, PathBuf},
     result::Result as StdResult,
-    sync::mpsc::{self, Sender},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
     thread,
 };
 use visitor::{BranchVisitorBuilder, TraversalState};
 
+#[cfg(unix)]
+use crate::render::context::owner::Owner;
+
+#[cfg(unix)]
+use node::MainOwner;
+
+#[cfg(unix)]
+use std::cmp::Reverse;
+
 /// Operations to handle and display aggregate file counts based on their type.
 mod count;
 
+/// Comparison of [Tree] against a previously saved snapshot.
+pub mod diff;
+
 /// Display variants for [Tree].
 pub mod display;
 
 /// Errors related to traversal, [Tree] construction, and the like.
 pub mod error;
 
+/// Stopping traversal early on SIGINT.
+pub mod interrupt;
+
+/// Reading in ncdu JSON exports as an alternative to traversing the filesystem.
+mod ncdu;
+
 /// Contains components of the [`Tree`] data structure that derive from [`DirEntry`].
 ///
 /// [`Tree`]: Tree
 /// [`DirEntry`]: ignore::DirEntry
 pub mod node;
 
+/// Reporting the progress of traversal on stderr.
+mod progress;
+
+/// Saving and loading of snapshots of [Tree] so that it can be re-rendered without traversal.
+mod snapshot;
+
 /// Custom visitor that operates on each thread during filesystem traversal.
 mod visitor;
 
+/// Incremental updates of [Tree] in response to filesystem events.
+mod watch;
+
 /// Virtual data structure that represents local file-system hierarchy.
 pub struct Tree<T>
 where
@@ -51,6 +79,10 @@ where
     root_id: NodeId,
     ctx: Context,
     display_variant: PhantomData<T>,
+
+    /// The [Tree] as it was assembled before anything was filtered out, kept in `--watch` mode
+    /// such that it can be brought up to date and finalized anew, see [Self::refresh].
+    assembled: Option<Arena<Node>>,
 }
 
 pub type Result<T> = StdResult<T, Error>;
@@ -66,14 +98,56 @@ where
             root_id,
             ctx,
             display_variant: PhantomData,
+            assembled: None,
         }
     }
 
-    /// Initiates file-system traversal and [Tree construction].
-    pub fn try_init(mut ctx: Context) -> Result<Self> {
+    /// Initiates file-system traversal and [Tree construction]. Fails if there is nothing to show
+    /// beneath the root.
+    pub fn try_init(ctx: Context) -> Result<Self> {
+        let tree = Self::try_assemble(ctx)?;
+
+        if tree.is_stump() {
+            return Err(Error::NoMatches);
+        }
+
+        Ok(tree)
+    }
+
+    /// Same as [Tree::try_init] except that a [Tree] with nothing but the root is fine.
+    pub fn try_assemble(mut ctx: Context) -> Result<Self> {
         let mut column_properties = ColumnProperties::from(&ctx);
 
-        let (arena, root_id) = Self::traverse(&ctx, &mut column_properties)?;
+        let (mut arena, root_id) = if let Some(ref export) = ctx.import_ncdu {
+            let nodes = ncdu::read(export, &ctx)?;
+            let (arena, root_id) = Self::from_nodes(nodes, &ctx, &mut column_properties)?;
+
+            // The root of an ncdu export need not exist locally.
+            ctx.set_dir(arena[root_id].get().path().to_path_buf());
+
+            (arena, root_id)
+        } else if let Some(ref snapshot) = ctx.load {
+            let (header, nodes) = snapshot::load(snapshot, &ctx)?;
+            let (arena, root_id) = Self::from_nodes(nodes, &ctx, &mut column_properties)?;
+
+            // The root of a snapshot need not exist anymore.
+            ctx.set_dir(arena[root_id].get().path().to_path_buf());
+            ctx.follow = header.follow;
+
+            (arena, root_id)
+        } else {
+            Self::traverse(&ctx, &mut column_properties)?
+        };
+
+        // The snapshot is taken before anything is filtered out such that it can be rendered with
+        // any filter later on. A partial tree would make for a misleading snapshot.
+        if let Some(path) = ctx.save.as_ref().filter(|_| !interrupt::is_interrupted()) {
+            snapshot::save(path, &arena, root_id, &ctx)?;
+        }
+
+        let assembled = ctx.watch.then(|| arena.clone());
+
+        Self::finalize(&mut arena, root_id, &mut column_properties, &ctx);
 
         ctx.update_column_properties(&column_properties);
 
@@ -81,11 +155,8 @@ where
             ctx.set_window_width();
         }
 
-        let tree = Self::new(arena, root_id, ctx);
-
-        if tree.is_stump() {
-            return Err(Error::NoMatches);
-        }
+        let mut tree = Self::new(arena, root_id, ctx);
+        tree.assembled = assembled;
 
         Ok(tree)
     }
@@ -100,98 +171,286 @@ where
             .is_none()
     }
 
+    /// Sorts the contents of every directory anew according to the sort-order in [Context]. Used
+    /// when the sort-order is changed after the [Tree] was constructed.
+    pub fn resort(&mut self) {
+        let node_comparator = node::cmp::comparator(&self.ctx);
+
+        let dirs = self
+            .root_id
+            .descendants(&self.arena)
+            .filter(|node_id| self.arena[*node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        for dir_id in dirs {
+            self.sort_children(dir_id, &node_comparator);
+        }
+    }
+
+    /// Sorts the immediate children of the directory at `dir_id`.
+    fn sort_children(&mut self, dir_id: NodeId, node_comparator: &NodeComparator) {
+        let mut children = dir_id.children(&self.arena).collect::<Vec<_>>();
+
+        children.sort_by(|id_a, id_b| {
+            let node_a = self.arena[*id_a].get();
+            let node_b = self.arena[*id_b].get();
+            node_comparator(node_a, node_b)
+        });
+
+        for child_id in children {
+            child_id.detach(&mut self.arena);
+            dir_id.append(child_id, &mut self.arena);
+        }
+    }
+
     /// Grab a reference to [Context].
     pub const fn context(&self) -> &Context {
         &self.ctx
     }
 
+    /// Grab a mutable reference to [Context].
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
     /// Grab a reference to `root_id`.
-    const fn root_id(&self) -> NodeId {
+    pub const fn root_id(&self) -> NodeId {
         self.root_id
     }
 
     /// Grabs a reference to `arena`.
-    const fn arena(&self) -> &Arena<Node> {
+    pub const fn arena(&self) -> &Arena<Node> {
         &self.arena
     }
 
     /// Parallel traversal of the root_id directory and its contents. Parallel traversal relies on
     /// `WalkParallel`. Any filesystem I/O or related system calls are expected to occur during
     /// parallel traversal; post-processing post-processing of all directory entries should
-    /// be completely CPU-bound.
+    /// be completely CPU-bound. If traversal is interrupted by SIGINT, the [Tree] is assembled out
+    /// of whatever was gathered up to that point and directories that weren't fully read are
+    /// marked as such. The [Tree] is yet to be finalized, see [Self::finalize].
     fn traverse(
         ctx: &Context,
         column_properties: &mut ColumnProperties,
     ) -> Result<(Arena<Node>, NodeId)> {
-        let walker = WalkParallel::try_from(ctx)?;
-        let (tx, rx) = mpsc::channel();
-
-        thread::scope(|s| {
-            let res = s.spawn(move || {
-                let mut tree = Arena::new();
-                let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
-                let mut root_id_id = None;
-
-                while let Ok(TraversalState::Ongoing(node)) = rx.recv() {
-                    if node.is_dir() {
-                        let node_path = node.path();
-
-                        if !branches.contains_key(node_path) {
-                            branches.insert(node_path.to_owned(), vec![]);
+        let outstanding = Outstanding::default();
+
+        Self::walk(ctx, &outstanding, false, |rx| {
+            let mut tree = Arena::new();
+            let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
+            let mut root_id = None;
+            let mut progress = Progress::init(ctx);
+
+            loop {
+                match rx.recv_timeout(progress::INTERVAL) {
+                    Ok(TraversalState::Ongoing(node)) => {
+                        if let Some(ref mut progress) = progress {
+                            progress.update(&node);
                         }
 
-                        if node.depth() == 0 {
-                            root_id_id = Some(tree.new_node(node));
-                            continue;
+                        Self::insert_node(&mut tree, &mut branches, &mut root_id, node)?;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Some(ref mut progress) = progress {
+                            progress.tick();
                         }
                     }
+                    // Listings are only reported when asked for.
+                    Ok(TraversalState::Listed(..) | TraversalState::Unreadable(_)) => (),
+                    Ok(TraversalState::Done) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            drop(progress);
 
-                    let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
+            if interrupt::is_interrupted() {
+                let incomplete =
+                    outstanding.incomplete(|dir| branches.get(dir).map_or(0, Vec::len));
 
-                    let node_id = tree.new_node(node);
+                for node in tree.iter_mut() {
+                    let node = node.get_mut();
 
-                    if branches
-                        .get_mut(&parent)
-                        .map(|mut_ref| mut_ref.push(node_id))
-                        .is_none()
-                    {
-                        branches.insert(parent, vec![]);
+                    if incomplete.contains(node.path()) {
+                        node.mark_incomplete();
                     }
                 }
+            }
 
-                let root_id = root_id_id.ok_or(Error::MissingRoot)?;
-                let node_comparator = node::cmp::comparator(ctx);
-                let mut inodes = HashSet::new();
+            let root_id = root_id.ok_or(Error::MissingRoot)?;
 
-                Self::assemble_tree(
-                    &mut tree,
-                    root_id,
-                    &mut branches,
-                    &node_comparator,
-                    &mut inodes,
-                    column_properties,
-                    ctx,
-                );
+            Self::assemble(&mut tree, root_id, &mut branches, column_properties, ctx);
 
-                if ctx.prune || ctx.file_type != Some(FileType::Dir) {
-                    Self::prune_directories(root_id, &mut tree);
-                }
+            Ok((tree, root_id))
+        })
+    }
 
-                if ctx.dirs_only {
-                    Self::filter_directories(root_id, &mut tree);
-                }
+    /// Walks the root directory in parallel on other threads while `receive` consumes whatever
+    /// is visited on the current one. Entries queued up for a visit are recorded in
+    /// `outstanding`; whether listings are reported as well is up to `report_listings`, see
+    /// [TraversalState::Listed].
+    fn walk<R, F>(
+        ctx: &Context,
+        outstanding: &Outstanding,
+        report_listings: bool,
+        receive: F,
+    ) -> Result<R>
+    where
+        F: FnOnce(Receiver<TraversalState>) -> Result<R>,
+    {
+        let walker = parallel_walker(ctx)?;
+        let (tx, rx) = mpsc::channel();
+        let mut visitor_builder = BranchVisitorBuilder::new(
+            ctx,
+            Sender::clone(&tx),
+            Outstanding::clone(outstanding),
+            report_listings,
+        );
+        let _guard = TraversalGuard::begin();
 
-                Ok::<(Arena<Node>, NodeId), Error>((tree, root_id))
+        thread::scope(|s| {
+            s.spawn(move || {
+                walker.visit(&mut visitor_builder);
+
+                // The receiver is gone if it ran into an error.
+                let _ = tx.send(TraversalState::Done);
             });
 
-            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx));
+            receive(rx)
+        })
+    }
 
-            walker.visit(&mut visitor_builder);
+    /// Constructs the [Tree] out of nodes from a source other than the filesystem such as an
+    /// ncdu export or a snapshot. The nodes are expected in pre-order and are assembled just like
+    /// those produced by [Self::traverse].
+    fn from_nodes(
+        nodes: Vec<Node>,
+        ctx: &Context,
+        column_properties: &mut ColumnProperties,
+    ) -> Result<(Arena<Node>, NodeId)> {
+        let mut tree = Arena::new();
+        let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
+        let mut root_id = None;
 
-            tx.send(TraversalState::Done).unwrap();
+        for node in nodes {
+            Self::insert_node(&mut tree, &mut branches, &mut root_id, node)?;
+        }
 
-            res.join().unwrap()
-        })
+        let root_id = root_id.ok_or(Error::MissingRoot)?;
+
+        Self::assemble(&mut tree, root_id, &mut branches, column_properties, ctx);
+
+        Ok((tree, root_id))
+    }
+
+    /// Adds a [Node] to the arena and records it as a child of its parent directory so that
+    /// [Self::assemble_tree] can later link everything together. Parent directories are
+    /// expected to be inserted before their children.
+    fn insert_node(
+        tree: &mut Arena<Node>,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+        root_id: &mut Option<NodeId>,
+        node: Node,
+    ) -> Result<()> {
+        if node.is_dir() {
+            let node_path = node.path();
+
+            if !branches.contains_key(node_path) {
+                branches.insert(node_path.to_owned(), vec![]);
+            }
+
+            if node.depth() == 0 {
+                *root_id = Some(tree.new_node(node));
+                return Ok(());
+            }
+        }
+
+        let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
+
+        let node_id = tree.new_node(node);
+
+        if branches
+            .get_mut(&parent)
+            .map(|mut_ref| mut_ref.push(node_id))
+            .is_none()
+        {
+            branches.insert(parent, vec![]);
+        }
+
+        Ok(())
+    }
+
+    /// Assembles the [Tree] from the nodes that were collected, which computes the aggregate sizes
+    /// of directories, without leaving anything out.
+    fn assemble(
+        tree: &mut Arena<Node>,
+        root_id: NodeId,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) {
+        let node_comparator = node::cmp::comparator(ctx);
+
+        Self::rank_hard_links(tree, branches);
+
+        Self::assemble_tree(
+            tree,
+            root_id,
+            branches,
+            &node_comparator,
+            column_properties,
+            ctx,
+        );
+    }
+
+    /// Applies pruning and filtering to the assembled [Tree] and computes whatever else is to be
+    /// displayed alongside each [Node].
+    fn finalize(
+        tree: &mut Arena<Node>,
+        root_id: NodeId,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) {
+        let node_comparator = node::cmp::comparator(ctx);
+
+        #[cfg(unix)]
+        for &owner in &ctx.owner {
+            Self::compute_main_owners(root_id, tree, owner, column_properties, ctx);
+        }
+
+        if ctx.min_size().is_some() || ctx.max_size().is_some() {
+            Self::filter_sizes(root_id, tree, ctx);
+        }
+
+        Self::prune_directories(root_id, tree, ctx);
+
+        if let Some(cutoff) = ctx.collapse {
+            Self::collapse_entries(
+                root_id,
+                tree,
+                cutoff,
+                &node_comparator,
+                column_properties,
+                ctx,
+            );
+        }
+
+        if ctx.shared {
+            Self::compute_shared_sizes(root_id, tree, column_properties, ctx);
+        }
+
+        if ctx.counts {
+            for node_id in root_id.descendants(tree) {
+                Self::update_count_width(column_properties, tree[node_id].get(), ctx.human);
+            }
+        }
+
+        if ctx.dirs_only {
+            Self::filter_directories(root_id, tree);
+        }
+
+        if ctx.percent {
+            Self::compute_shares(root_id, tree);
+        }
     }
 
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
@@ -202,7 +461,6 @@ where
         current_node_id: NodeId,
         branches: &mut HashMap<PathBuf, Vec<NodeId>>,
         node_comparator: &NodeComparator,
-        inode_set: &mut HashSet<Inode>,
         column_properties: &mut ColumnProperties,
         ctx: &Context,
     ) {
@@ -210,7 +468,24 @@ where
 
         let mut children = branches.remove(current_node.path()).unwrap();
 
-        let mut dir_size = FileSize::new(0, ctx.disk_usage, ctx.human, ctx.unit);
+        // A directory's own size is only known if its own blocks are accounted for.
+        let own_bytes = |file_size: Option<&FileSize>| file_size.map_or(0, |fs| fs.bytes);
+
+        let mut dir_size = FileSize::new(
+            own_bytes(current_node.file_size()),
+            ctx.disk_usage,
+            ctx.human,
+            ctx.unit,
+        );
+
+        let mut other_dir_size = FileSize::new(
+            own_bytes(current_node.other_file_size()),
+            ctx.disk_usage.other(),
+            ctx.human,
+            ctx.unit,
+        );
+
+        let mut descendants = FileCount::default();
 
         for child_id in &children {
             let index = *child_id;
@@ -226,7 +501,6 @@ where
                     index,
                     branches,
                     node_comparator,
-                    inode_set,
                     column_properties,
                     ctx,
                 );
@@ -240,16 +514,11 @@ where
             #[cfg(not(unix))]
             Self::update_column_properties(column_properties, node);
 
-            // If a hard-link is already accounted for then don't increment parent dir size.
-            if let Some(inode) = node.inode() {
-                if inode.nlink > 1 && !inode_set.insert(inode) {
-                    continue;
-                }
-            }
+            descendants.update(node);
+            descendants.update_from_count(node.descendants());
 
-            if let Some(file_size) = node.file_size() {
-                dir_size += file_size;
-            }
+            dir_size.bytes += node.charged_bytes(ctx.hardlinks);
+            other_dir_size.bytes += node.charged_other_bytes(ctx.hardlinks);
         }
 
         if dir_size.bytes > 0 {
@@ -260,6 +529,16 @@ where
             dir.set_file_size(dir_size);
         }
 
+        if other_dir_size.bytes > 0 {
+            let dir = tree[current_node_id].get_mut();
+
+            other_dir_size.precompute_unpadded_display();
+
+            dir.set_other_file_size(other_dir_size);
+        }
+
+        tree[current_node_id].get_mut().set_descendants(descendants);
+
         let dir = tree[current_node_id].get();
 
         #[cfg(unix)]
@@ -280,18 +559,110 @@ where
         }
     }
 
-    /// Function to remove empty directories.
-    fn prune_directories(root_id_id: NodeId, tree: &mut Arena<Node>) {
-        let mut to_prune = vec![];
+    /// Ranks each link of every file with multiple hard links among all of its links that were
+    /// collected in `branches` by name order. See [HardLink].
+    fn rank_hard_links(tree: &mut Arena<Node>, branches: &HashMap<PathBuf, Vec<NodeId>>) {
+        let mut links: HashMap<Inode, Vec<NodeId>> = HashMap::new();
 
-        for node_id in root_id_id.descendants(tree).skip(1) {
+        for node_id in branches.values().flatten() {
+            let node = tree[*node_id].get();
+
+            if let Some(inode) = node.inode().filter(|_| node.is_hard_linked()) {
+                links.entry(inode).or_default().push(*node_id);
+            }
+        }
+
+        for mut node_ids in links.into_values() {
+            node_ids.sort_by(|id_a, id_b| tree[*id_a].get().path().cmp(tree[*id_b].get().path()));
+
+            let count = node_ids.len() as u64;
+
+            for (rank, node_id) in (0..).zip(node_ids) {
+                tree[node_id]
+                    .get_mut()
+                    .set_hard_link(HardLink { rank, count });
+            }
+        }
+    }
+
+    /// Computes how many bytes each [Node] shares through hard links with entries outside of it.
+    /// A file with multiple hard links shares all of its bytes. A directory shares the bytes of
+    /// the hard-linked files within it that have links elsewhere, which for links that all lie
+    /// within the tree are the directories beneath the deepest one that contains every link.
+    fn compute_shared_sizes(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) {
+        let mut links: HashMap<Inode, Vec<NodeId>> = HashMap::new();
+
+        for node_id in root_id.descendants(tree) {
             let node = tree[node_id].get();
 
-            if !node.is_dir() {
-                continue;
+            if let Some(inode) = node.inode().filter(|_| node.is_hard_linked()) {
+                links.entry(inode).or_default().push(node_id);
+            }
+        }
+
+        let mut shared: HashMap<NodeId, u64> = HashMap::new();
+
+        for (inode, node_ids) in links {
+            let bytes = tree[node_ids[0]].get().file_size().map_or(0, |fs| fs.bytes);
+
+            let ancestries = node_ids
+                .iter()
+                .map(|node_id| {
+                    let mut ancestors = node_id.ancestors(tree).collect::<Vec<_>>();
+                    ancestors.reverse();
+                    ancestors
+                })
+                .collect::<Vec<_>>();
+
+            // Number of ancestors, root first, that contain every link.
+            let common = if (node_ids.len() as u64) < inode.nlink {
+                0
+            } else {
+                let first = &ancestries[0];
+
+                (0..first.len())
+                    .take_while(|i| ancestries.iter().all(|a| a.get(*i) == Some(&first[*i])))
+                    .count()
+            };
+
+            let sharing = ancestries
+                .iter()
+                .flat_map(|ancestors| &ancestors[common..])
+                .collect::<HashSet<_>>();
+
+            for node_id in sharing {
+                *shared.entry(*node_id).or_default() += bytes;
             }
+        }
+
+        for (node_id, bytes) in shared {
+            let mut file_size = FileSize::new(bytes, ctx.disk_usage, ctx.human, ctx.unit);
+            file_size.precompute_unpadded_display();
+
+            let node = tree[node_id].get_mut();
+            node.set_shared_size(file_size);
+
+            #[cfg(unix)]
+            Self::update_column_properties(column_properties, node, ctx.long);
+
+            #[cfg(not(unix))]
+            Self::update_column_properties(column_properties, node);
+        }
+    }
 
-            if node_id.children(tree).count() == 0 {
+    /// Function to remove empty directories, see [Node::is_prunable].
+    fn prune_directories(root_id_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let mut to_prune = vec![];
+
+        for node_id in root_id_id.descendants(tree).skip(1) {
+            let is_occupied = node_id.children(tree).next().is_some();
+
+            if tree[node_id].get().is_prunable(ctx, is_occupied) {
                 to_prune.push(node_id);
             }
         }
@@ -301,10 +672,264 @@ where
         }
 
         for node_id in to_prune {
-            node_id.remove_subtree(tree);
+            Self::remove_counted(node_id, tree);
         }
 
-        Self::prune_directories(root_id_id, tree);
+        Self::prune_directories(root_id_id, tree, ctx);
+    }
+
+    /// Removes entries whose size falls outside of `--min-size` and `--max-size`. Directories are
+    /// judged by their aggregate size, though those too large are kept so long as they lead to
+    /// entries that are in range. Directories left empty are then removed as well.
+    fn filter_sizes(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let min_size = ctx.min_size().unwrap_or(u64::MIN);
+        let max_size = ctx.max_size().unwrap_or(u64::MAX);
+
+        let mut to_remove = vec![];
+        let mut emptied = vec![];
+
+        for node_id in root_id.descendants(tree).skip(1) {
+            let node = tree[node_id].get();
+            let bytes = node.file_size().map_or(0, |fs| fs.bytes);
+
+            if bytes < min_size || (bytes > max_size && !node.is_dir()) {
+                to_remove.push(node_id);
+            } else if bytes > max_size {
+                emptied.push(node_id);
+            }
+        }
+
+        for node_id in to_remove {
+            if node_id.is_removed(tree) {
+                continue;
+            }
+
+            emptied.extend(tree[node_id].parent());
+            Self::remove_counted(node_id, tree);
+        }
+
+        while let Some(node_id) = emptied.pop() {
+            if node_id == root_id || node_id.is_removed(tree) {
+                continue;
+            }
+
+            let is_occupied = node_id.children(tree).next().is_some();
+
+            if !tree[node_id].get().is_prunable(ctx, is_occupied) {
+                continue;
+            }
+
+            emptied.extend(tree[node_id].parent());
+            Self::remove_counted(node_id, tree);
+        }
+    }
+
+    /// Removes the [Node] at `node_id` along with everything beneath it such that none of it
+    /// counts towards the descendants of its ancestors any longer.
+    fn remove_counted(node_id: NodeId, tree: &mut Arena<Node>) {
+        let mut removed = FileCount::new();
+
+        let node = tree[node_id].get();
+        removed.update(node);
+        removed.update_from_count(node.descendants());
+
+        let ancestors = node_id.ancestors(tree).skip(1).collect::<Vec<_>>();
+
+        for ancestor_id in ancestors {
+            let ancestor = tree[ancestor_id].get_mut();
+            let mut descendants = ancestor.descendants();
+            descendants.remove_count(removed);
+            ancestor.set_descendants(descendants);
+        }
+
+        node_id.remove_subtree(tree);
+    }
+
+    /// Merges the entries of each directory that are smaller than `cutoff` into a single synthetic
+    /// [Node]. Nothing is merged unless there are at least two such entries. A synthetic [Node]
+    /// left over from before is merged along with them regardless of its size.
+    fn collapse_entries(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        cutoff: Cutoff,
+        node_comparator: &NodeComparator,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) {
+        let bytes = |node: &Node| node.file_size().map_or(0, |fs| fs.bytes);
+
+        let dirs = root_id
+            .descendants(tree)
+            .filter(|node_id| tree[*node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        for dir_id in dirs {
+            // Directories merged into one of their parent's synthetic nodes are gone.
+            if dir_id.is_removed(tree) {
+                continue;
+            }
+
+            let dir = tree[dir_id].get();
+            let dir_bytes = bytes(dir);
+
+            let to_collapse = dir_id
+                .children(tree)
+                .filter(|child_id| {
+                    let child = tree[*child_id].get();
+                    child.is_collapsed() || !cutoff.is_above(bytes(child), dir_bytes, ctx.unit)
+                })
+                .collect::<Vec<_>>();
+
+            if to_collapse.len() < 2 {
+                continue;
+            }
+
+            let mut collapsed = FileCount::new();
+            let mut descendants = FileCount::new();
+            let mut file_size = FileSize::new(0, ctx.disk_usage, ctx.human, ctx.unit);
+            let mut other_file_size = FileSize::new(0, ctx.disk_usage.other(), ctx.human, ctx.unit);
+
+            for child_id in &to_collapse {
+                let child = tree[*child_id].get();
+
+                // A synthetic node left over from before already counts its descendants.
+                if let Some(merged) = child.collapsed() {
+                    collapsed.update_from_count(merged);
+                } else {
+                    collapsed.update(child);
+                }
+
+                descendants.update_from_count(child.descendants());
+
+                file_size.bytes += child.charged_bytes(ctx.hardlinks);
+                other_file_size.bytes += child.charged_other_bytes(ctx.hardlinks);
+            }
+
+            for child_id in to_collapse {
+                child_id.remove_subtree(tree);
+            }
+
+            file_size.precompute_unpadded_display();
+
+            let other_file_size = (other_file_size.bytes > 0).then(|| {
+                other_file_size.precompute_unpadded_display();
+                other_file_size
+            });
+
+            let node = Node::new_collapsed(
+                tree[dir_id].get().path(),
+                tree[dir_id].get().depth() + 1,
+                collapsed,
+                descendants,
+                file_size,
+                other_file_size,
+            );
+
+            #[cfg(unix)]
+            Self::update_column_properties(column_properties, &node, ctx.long);
+
+            #[cfg(not(unix))]
+            Self::update_column_properties(column_properties, &node);
+
+            let collapsed_id = tree.new_node(node);
+            dir_id.append(collapsed_id, tree);
+
+            let mut children = dir_id.children(tree).collect::<Vec<_>>();
+
+            children.sort_by(|id_a, id_b| {
+                let node_a = tree[*id_a].get();
+                let node_b = tree[*id_b].get();
+                node_comparator(node_a, node_b)
+            });
+
+            for child_id in children {
+                child_id.detach(tree);
+                dir_id.append(child_id, tree);
+            }
+        }
+    }
+
+    /// Determines the [MainOwner] by user or by group of every [Node], which for directories is
+    /// whoever owns the most of the disk usage of the files beneath them at any depth.
+    #[cfg(unix)]
+    fn compute_main_owners(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        owner: Owner,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) {
+        let mut names = HashMap::new();
+        Self::tally_owners(root_id, tree, owner, &mut names, column_properties, ctx);
+    }
+
+    /// Sets the [MainOwner] of the [Node] at `node_id` and of all of its descendants. Returns the
+    /// number of bytes of the files beneath it that each owner accounts for. The names of owners
+    /// are looked up once and kept in `names`.
+    #[cfg(unix)]
+    fn tally_owners(
+        node_id: NodeId,
+        tree: &mut Arena<Node>,
+        owner: Owner,
+        names: &mut HashMap<u32, String>,
+        column_properties: &mut ColumnProperties,
+        ctx: &Context,
+    ) -> HashMap<u32, u64> {
+        let mut tally = HashMap::new();
+        let mut owned = vec![];
+
+        let children = node_id.children(tree).collect::<Vec<_>>();
+
+        for child_id in children {
+            if tree[child_id].get().is_dir() {
+                for (id, bytes) in
+                    Self::tally_owners(child_id, tree, owner, names, column_properties, ctx)
+                {
+                    *tally.entry(id).or_insert(0) += bytes;
+                }
+
+                continue;
+            }
+
+            let child = tree[child_id].get();
+
+            let Some(id) = child.owner_id(owner) else {
+                continue;
+            };
+
+            *tally.entry(id).or_insert(0) += child.charged_bytes(ctx.hardlinks);
+            owned.push((child_id, id, None));
+        }
+
+        let total = tally.values().sum::<u64>();
+
+        // Ties go to the lowest id. Directories without any files go by their own owner.
+        let main_owner = tally
+            .iter()
+            .max_by_key(|(id, bytes)| (**bytes, Reverse(**id)))
+            .map(|(id, bytes)| (*id, (total > 0).then(|| *bytes as f64 / total as f64)))
+            .or_else(|| tree[node_id].get().owner_id(owner).map(|id| (id, None)));
+
+        if let Some((id, share)) = main_owner {
+            owned.push((node_id, id, share));
+        }
+
+        for (node_id, id, share) in owned {
+            let name = names.entry(id).or_insert_with(|| owner.name_of(id)).clone();
+
+            let max_width = match owner {
+                Owner::User => &mut column_properties.max_user_width,
+                Owner::Group => &mut column_properties.max_group_width,
+            };
+
+            *max_width = (*max_width).max(name.chars().count());
+
+            tree[node_id]
+                .get_mut()
+                .set_main_owner(MainOwner { owner, name, share });
+        }
+
+        tally
     }
 
     /// Filter for only directories.
@@ -322,6 +947,51 @@ where
         }
     }
 
+    /// Updates [ColumnProperties] with the widths of the numbers of descendants of the provided
+    /// [Node] if it is a directory.
+    fn update_count_width(col_props: &mut ColumnProperties, node: &Node, human: bool) {
+        if !node.is_dir() {
+            return;
+        }
+
+        let FileCount {
+            num_dirs,
+            num_files,
+            num_links,
+        } = node.descendants();
+
+        for num in [num_dirs, num_files, num_links] {
+            let width = FileCount::format_num(num, human).len();
+
+            if width > col_props.max_count_width {
+                col_props.max_count_width = width;
+            }
+        }
+    }
+
+    /// Computes the [Share] of the parent directory and of the root of every [Node].
+    fn compute_shares(root_id: NodeId, tree: &mut Arena<Node>) {
+        let bytes = |tree: &Arena<Node>, node_id: NodeId| {
+            tree[node_id].get().file_size().map_or(0, |fs| fs.bytes)
+        };
+
+        let root_bytes = bytes(tree, root_id);
+
+        let descendants = root_id.descendants(tree).collect::<Vec<_>>();
+
+        for node_id in descendants {
+            let node_bytes = bytes(tree, node_id);
+
+            let parent_bytes = tree[node_id]
+                .parent()
+                .map_or(node_bytes, |parent_id| bytes(tree, parent_id));
+
+            let share = Share::new(node_bytes, parent_bytes, root_bytes);
+
+            tree[node_id].get_mut().set_share(share);
+        }
+    }
+
     /// Compute total number of files for a single directory without recurring into child
     /// directories. Files are grouped into three categories: directories, regular files, and
     /// symlinks.
@@ -338,7 +1008,13 @@ where
     /// Updates [ColumnProperties] with provided [Node].
     #[cfg(unix)]
     fn update_column_properties(col_props: &mut ColumnProperties, node: &Node, long: bool) {
-        if let Some(file_size) = node.file_size() {
+        let file_sizes = node
+            .file_size()
+            .into_iter()
+            .chain(node.other_file_size())
+            .chain(node.shared_size());
+
+        for file_size in file_sizes {
             let file_size_cols = file_size.size_columns;
 
             if file_size_cols > col_props.max_size_width {
@@ -376,7 +1052,13 @@ where
     /// Updates [ColumnProperties] with provided [Node].
     #[cfg(not(unix))]
     fn update_column_properties(col_props: &mut ColumnProperties, node: &Node) {
-        if let Some(file_size) = node.file_size() {
+        let file_sizes = node
+            .file_size()
+            .into_iter()
+            .chain(node.other_file_size())
+            .chain(node.shared_size());
+
+        for file_size in file_sizes {
             let file_size_cols = file_size.size_columns;
 
             if file_size_cols > col_props.max_size_width {
@@ -386,32 +1068,45 @@ where
     }
 }
 
-impl TryFrom<&Context> for WalkParallel {
-    type Error = Error;
-
-    fn try_from(ctx: &Context) -> StdResult<Self, Self::Error> {
-        let root_id = fs::canonicalize(ctx.dir())?;
+/// Configures the parallel traversal of the root directory. Entries queued up for a visit are
+/// recorded in `outstanding`.
+fn parallel_walker(ctx: &Context) -> Result<WalkParallel> {
+    let root_id = fs::canonicalize(ctx.dir())?;
 
-        fs::metadata(&root_id)
-            .map_err(|e| Error::DirNotFound(format!("{}: {e}", root_id.display())))?;
+    fs::metadata(&root_id)
+        .map_err(|e| Error::DirNotFound(format!("{}: {e}", root_id.display())))?;
 
-        let mut builder = WalkBuilder::new(root_id);
+    Ok(walk_builder(ctx, &root_id, true)?.build_parallel())
+}
 
-        builder
-            .follow_links(ctx.follow)
-            .git_ignore(!ctx.no_ignore)
-            .hidden(!ctx.hidden)
-            .overrides(ctx.no_git_override()?)
-            .threads(ctx.threads);
+/// Configures a [WalkBuilder] rooted at `path` to respect the filtering options of [Context].
+/// Entries that make it past the filters are recorded as queued up on the visiting thread if
+/// `track_outstanding` is set, see [interrupt::Listings].
+fn walk_builder(ctx: &Context, path: &Path, track_outstanding: bool) -> Result<WalkBuilder> {
+    let mut builder = WalkBuilder::new(path);
+
+    builder
+        .follow_links(ctx.follow)
+        .git_ignore(!ctx.no_ignore)
+        .hidden(!ctx.hidden)
+        .overrides(ctx.no_git_override()?)
+        .threads(ctx.threads);
+
+    let predicate = ctx.pattern_predicate()?;
+
+    if predicate.is_some() || track_outstanding {
+        builder.filter_entry(move |dir_entry| {
+            let is_match = predicate.as_ref().map_or(true, |predicate| {
+                predicate(dir_entry.path(), dir_entry.file_type().map(FileType::from))
+            });
 
-        if ctx.pattern.is_some() {
-            if ctx.glob || ctx.iglob {
-                builder.filter_entry(ctx.glob_predicate()?);
-            } else {
-                builder.filter_entry(ctx.regex_predicate()?);
+            if track_outstanding && is_match {
+                interrupt::queue();
             }
-        }
 
-        Ok(builder.build_parallel())
+            is_match
+        });
     }
+
+    Ok(builder)
 }
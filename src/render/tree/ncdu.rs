@@ -0,0 +1,244 @@
+use super::{error::Error, node::Node, Result};
+use crate::{
+    fs::inode::Inode,
+    render::{
+        context::{file::FileType, Context, Predicate},
+        disk_usage::file_size::{DiskUsage, FileSize},
+    },
+};
+use serde_json::{Map, Value};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Bitmask for the file-type portion of `st_mode`.
+const S_IFMT: u64 = 0o170_000;
+
+/// `st_mode` file-type bits for a directory.
+const S_IFDIR: u64 = 0o040_000;
+
+/// `st_mode` file-type bits for a symbolic link.
+const S_IFLNK: u64 = 0o120_000;
+
+/// Reads an ncdu JSON export from `path`, or from stdin if `path` is `-`, and returns a [Node]
+/// for every entry in pre-order such that parent directories always precede their children.
+/// Entries that ncdu marked as excluded as well as those that don't survive `--hidden` or
+/// `--pattern` are dropped.
+pub fn read(path: &Path, ctx: &Context) -> Result<Vec<Node>> {
+    let export: Value = if path == Path::new("-") {
+        serde_json::from_reader(BufReader::new(io::stdin().lock()))
+    } else {
+        serde_json::from_reader(BufReader::new(File::open(path)?))
+    }
+    .map_err(|e| Error::NcduImport(e.to_string()))?;
+
+    let root = match export.as_array().map(Vec::as_slice) {
+        Some([Value::Number(major), _minor, _metadata, root, ..]) if major.as_u64() == Some(1) => {
+            root
+        }
+        Some([Value::Number(major), ..]) => {
+            return Err(Error::NcduImport(format!(
+                "unsupported major version {major}"
+            )))
+        }
+        _ => return Err(Error::NcduImport(String::from("malformed export"))),
+    };
+
+    let predicate = ctx.pattern_predicate()?;
+
+    let mut nodes = vec![];
+
+    let mut reader = Reader {
+        ctx,
+        predicate: predicate.as_ref(),
+        nodes: &mut nodes,
+    };
+
+    reader.visit(root, None, 0, 0)?;
+
+    Ok(nodes)
+}
+
+/// Walks the nested arrays of an ncdu export.
+struct Reader<'a> {
+    ctx: &'a Context,
+    predicate: Option<&'a Predicate>,
+    nodes: &'a mut Vec<Node>,
+}
+
+impl Reader<'_> {
+    /// Visits an entry of the export. Directories are represented as arrays whose first element
+    /// is the info block of the directory itself and whose remaining elements are its children.
+    /// All other files are represented solely by their info block.
+    fn visit(
+        &mut self,
+        entry: &Value,
+        parent: Option<&Path>,
+        parent_dev: u64,
+        depth: usize,
+    ) -> Result<()> {
+        let (info, children) = match entry {
+            Value::Array(items) => match items.split_first() {
+                Some((Value::Object(info), children)) => (info, Some(children)),
+                _ => return Err(Error::NcduImport(String::from("malformed directory"))),
+            },
+            Value::Object(info) => (info, None),
+            _ => return Err(Error::NcduImport(String::from("malformed entry"))),
+        };
+
+        if info.contains_key("excluded") {
+            return Ok(());
+        }
+
+        let name = info
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::NcduImport(String::from("entry without a name")))?;
+
+        if depth > 0 && !self.ctx.hidden && name.starts_with('.') {
+            return Ok(());
+        }
+
+        let path = parent.map_or_else(|| PathBuf::from(name), |p| p.join(name));
+
+        let mode = get_u64(info, "mode");
+
+        let file_type = if children.is_some() {
+            Some(FileType::Dir)
+        } else {
+            match mode.map(|m| m & S_IFMT) {
+                Some(S_IFDIR) => Some(FileType::Dir),
+                Some(S_IFLNK) => Some(FileType::Link),
+                _ if get_bool(info, "notreg") => None,
+                _ => Some(FileType::File),
+            }
+        };
+
+        if depth > 0 {
+            if let Some(predicate) = self.predicate {
+                if !predicate(&path, file_type) {
+                    return Ok(());
+                }
+            }
+        }
+
+        let dev = get_u64(info, "dev").unwrap_or(parent_dev);
+
+        let node = self.node(info, path.clone(), depth, file_type, mode, dev);
+
+        self.nodes.push(node);
+
+        for child in children.into_iter().flatten() {
+            self.visit(child, Some(&path), dev, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Constructs a [Node] out of the info block of an entry.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    fn node(
+        &self,
+        info: &Map<String, Value>,
+        path: PathBuf,
+        depth: usize,
+        file_type: Option<FileType>,
+        mode: Option<u64>,
+        dev: u64,
+    ) -> Node {
+        let ctx = self.ctx;
+
+        let asize = get_u64(info, "asize").unwrap_or(0);
+        let dsize = get_u64(info, "dsize").unwrap_or(0);
+
+        let is_sized = |disk_usage| match file_type {
+            _ if ctx.suppress_size => false,
+            _ if disk_usage == DiskUsage::Inodes => true,
+            Some(FileType::File) => true,
+            Some(_) => ctx.own_blocks && disk_usage == DiskUsage::Physical,
+            None => false,
+        };
+
+        let sized = |disk_usage| {
+            if !is_sized(disk_usage) {
+                return None;
+            }
+
+            let bytes = match disk_usage {
+                DiskUsage::Logical => asize,
+                DiskUsage::Physical => dsize,
+                DiskUsage::Inodes => 1,
+            };
+            let mut file_size = FileSize::new(bytes, disk_usage, ctx.human, ctx.unit);
+            file_size.precompute_unpadded_display();
+            Some(file_size)
+        };
+
+        let file_size = sized(ctx.disk_usage);
+        let other_file_size = sized(ctx.disk_usage.other()).filter(|_| ctx.both_sizes);
+
+        let style = Node::style_for_path(&path, file_type);
+
+        let nlink = if get_bool(info, "hlnkc") {
+            get_u64(info, "nlink").unwrap_or(2)
+        } else {
+            1
+        };
+
+        let inode = get_u64(info, "ino").map(|ino| Inode::new(ino, dev, nlink));
+
+        let modified =
+            get_u64(info, "mtime").map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+
+        #[cfg(unix)]
+        let st_mode = mode.and_then(|m| u32::try_from(m).ok());
+
+        let mut node = Node::new(
+            path,
+            depth,
+            file_type,
+            file_size,
+            asize,
+            style,
+            None,
+            inode,
+            modified,
+            None,
+            None,
+            #[cfg(unix)]
+            st_mode,
+            #[cfg(unix)]
+            (dsize / 512),
+            #[cfg(unix)]
+            false,
+        );
+
+        if let Some(size) = other_file_size {
+            node.set_other_file_size(size);
+        }
+
+        #[cfg(unix)]
+        {
+            let id = |key| get_u64(info, key).and_then(|id| u32::try_from(id).ok());
+
+            if let (Some(uid), Some(gid)) = (id("uid"), id("gid")) {
+                node.set_ids(uid, gid);
+            }
+        }
+
+        node
+    }
+}
+
+/// Reads an unsigned integer field from an info block.
+fn get_u64(info: &Map<String, Value>, key: &str) -> Option<u64> {
+    info.get(key).and_then(Value::as_u64)
+}
+
+/// Reads a boolean field from an info block; absent fields are `false`.
+fn get_bool(info: &Map<String, Value>, key: &str) -> bool {
+    info.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
@@ -22,6 +22,8 @@ fn base_comparator(sort_type: SortType) -> Box<NodeComparator> {
         SortType::Name => Box::new(name_comparator),
         SortType::Size => Box::new(size_comparator),
         SortType::SizeRev => Box::new(size_rev_comparator),
+        SortType::Count => Box::new(count_comparator),
+        SortType::CountRev => Box::new(count_rev_comparator),
     }
 }
 
@@ -49,6 +51,16 @@ fn size_comparator(a: &Node, b: &Node) -> Ordering {
     b_size.cmp(&a_size)
 }
 
+/// Comparator that sorts [Node]s by number of descendants, fewest to most.
+fn count_rev_comparator(a: &Node, b: &Node) -> Ordering {
+    a.descendants().total().cmp(&b.descendants().total())
+}
+
+/// Comparator that sorts [Node]s by number of descendants, most to fewest.
+fn count_comparator(a: &Node, b: &Node) -> Ordering {
+    b.descendants().total().cmp(&a.descendants().total())
+}
+
 /// Comparator based on [Node] file names.
 fn name_comparator(a: &Node, b: &Node) -> Ordering {
     a.file_name().cmp(b.file_name())
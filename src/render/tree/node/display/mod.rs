@@ -20,8 +20,11 @@ impl Node {
         ctx: &Context,
     ) -> fmt::Result {
         let size = presenters::format_size(self, ctx);
+        let share = presenters::format_share(self, ctx);
+        let counts = presenters::format_counts(self, ctx);
         let padded_icon = presenters::format_padded_icon(self, ctx);
         let file_name = presenters::file_name(self);
+        let incomplete = presenters::format_incomplete(self, ctx);
 
         let pre = prefix.unwrap_or("");
 
@@ -32,15 +35,16 @@ impl Node {
                 nlink,
                 blocks,
                 timestamp,
+                owner,
             } = presenters::format_long(self, ctx);
 
             format!(
-                "{ino:<ino_padding$} {perms:<perms_padding$} {nlink} {blocks} {timestamp} {size} {pre}{padded_icon}{file_name}",
+                "{ino:<ino_padding$} {perms:<perms_padding$} {nlink} {blocks} {timestamp} {owner}{size} {share}{counts}{pre}{padded_icon}{file_name}{incomplete}",
                 ino_padding = ino.len(),
                 perms_padding = perms.len(),
             )
         } else {
-            format!("{size} {pre}{padded_icon}{file_name}")
+            format!("{size} {share}{counts}{pre}{padded_icon}{file_name}{incomplete}")
         };
 
         if ctx.truncate && ctx.window_width.is_some() {
@@ -58,6 +62,9 @@ impl Node {
         use std::{ffi::OsStr, path::Path};
 
         let size = presenters::format_size(self, ctx);
+        let share = presenters::format_share(self, ctx);
+        let counts = presenters::format_counts(self, ctx);
+        let incomplete = presenters::format_incomplete(self, ctx);
 
         let file = {
             let node_path = self.path();
@@ -81,15 +88,16 @@ impl Node {
                 nlink,
                 blocks,
                 timestamp,
+                owner,
             } = presenters::format_long(self, ctx);
 
             format!(
-                "{ino:<ino_padding$} {perms:<perms_padding$} {nlink} {blocks} {timestamp} {size}   {file}",
+                "{ino:<ino_padding$} {perms:<perms_padding$} {nlink} {blocks} {timestamp} {owner}{size}   {share}{counts}{file}{incomplete}",
                 ino_padding = ino.len(),
                 perms_padding = perms.len(),
             )
         } else {
-            format!("{size}   {file}")
+            format!("{size}   {share}{counts}{file}{incomplete}")
         };
 
         if ctx.truncate && ctx.window_width.is_some() {
@@ -105,6 +113,9 @@ impl Node {
     #[cfg(not(unix))]
     pub(super) fn flat(&self, f: &mut Formatter, ctx: &Context) -> fmt::Result {
         let size = presenters::format_size(self, ctx);
+        let share = presenters::format_share(self, ctx);
+        let counts = presenters::format_counts(self, ctx);
+        let incomplete = presenters::format_incomplete(self, ctx);
 
         let file = {
             let path = self
@@ -115,7 +126,7 @@ impl Node {
             Cow::from(path.display().to_string())
         };
 
-        let ln = format!("{size}   {file}");
+        let ln = format!("{size}   {share}{counts}{file}{incomplete}");
 
         if ctx.truncate && ctx.window_width.is_some() {
             let window_width = ctx.window_width.unwrap();
@@ -135,11 +146,14 @@ impl Node {
         ctx: &Context,
     ) -> fmt::Result {
         let size = presenters::format_size(self, ctx);
+        let share = presenters::format_share(self, ctx);
+        let counts = presenters::format_counts(self, ctx);
         let padded_icon = presenters::format_padded_icon(self, ctx);
         let file_name = presenters::file_name(self);
+        let incomplete = presenters::format_incomplete(self, ctx);
         let pre = prefix.unwrap_or("");
 
-        let ln = format!("{size} {pre}{padded_icon}{file_name}");
+        let ln = format!("{size} {share}{counts}{pre}{padded_icon}{file_name}{incomplete}");
 
         if ctx.truncate && ctx.window_width.is_some() {
             let window_width = ctx.window_width.unwrap();
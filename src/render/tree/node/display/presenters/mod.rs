@@ -0,0 +1,368 @@
+use crate::render::{
+    context::Context,
+    disk_usage::file_size::{DiskUsage, FileSize},
+    styles::{get_placeholder_style, PLACEHOLDER},
+    tree::{count::FileCount, node::Share, Node},
+};
+use ansi_term::Color;
+use std::borrow::Cow;
+
+/// Unit tests for the presentation of individual attributes.
+#[cfg(test)]
+mod test;
+
+/// Marker for directories that weren't fully read because traversal was interrupted.
+const INCOMPLETE: &str = "[incomplete]";
+
+/// Number of columns the ratio of physical to logical size occupies without its trailing `x`.
+const RATIO_WIDTH: usize = 7;
+
+/// Number of cells the bar of the percentage column spans.
+const BAR_WIDTH: usize = 20;
+
+/// Blocks filling a single cell of the bar in increments of an eighth.
+const EIGHTHS: [char; 8] = [
+    ' ', '\u{258F}', '\u{258E}', '\u{258D}', '\u{258C}', '\u{258B}', '\u{258A}', '\u{2589}',
+];
+
+/// Fills a whole cell of the bar.
+const FULL_BLOCK: char = '\u{2588}';
+
+/// Fills the cells of the bar that make up the rest of the parent directory's share of the root.
+const SHADE: char = '\u{2591}';
+
+#[cfg(unix)]
+use crate::render::{
+    context::{owner::Owner, time::Stamp},
+    styles::{self, error::Error},
+};
+
+#[cfg(unix)]
+use std::time::SystemTime;
+
+#[cfg(unix)]
+type StyleGetter = fn() -> Result<&'static ansi_term::Style, Error<'static>>;
+
+/// Attributes for the long view to be displayed.
+#[cfg(unix)]
+pub(super) struct LongAttrs {
+    pub ino: String,
+    pub perms: String,
+    pub nlink: String,
+    pub blocks: String,
+    pub timestamp: String,
+    pub owner: String,
+}
+
+/// Formats the parameters for the long view.
+#[cfg(unix)]
+#[inline]
+pub(super) fn format_long(node: &Node, ctx: &Context) -> LongAttrs {
+    let perms = match node.mode() {
+        Some(ref mode) if ctx.octal => Node::style_octal_permissions(mode),
+        Some(ref mode) => Node::style_sym_permissions(mode, node.has_xattrs()),
+        None => format_placeholder(if ctx.octal { 4 } else { 11 }),
+    };
+
+    let datetime = match ctx.time() {
+        Stamp::Created => node.created(),
+        Stamp::Accessed => node.accessed(),
+        Stamp::Modified => node.modified(),
+    };
+
+    let ino = format_num(node.ino(), ctx.max_ino_width, styles::get_ino_style);
+    let nlink = format_num(node.nlink(), ctx.max_nlink_width, styles::get_nlink_style);
+    let blocks = format_num(node.blocks(), ctx.max_block_width, styles::get_block_style);
+    let timestamp = format_datetime(datetime);
+    let owner = format_owner(node, ctx);
+
+    LongAttrs {
+        ino,
+        perms,
+        nlink,
+        blocks,
+        timestamp,
+        owner,
+    }
+}
+
+/// Builds the owner portion of the long view: the name of the user or group that owns a file, or
+/// for directories whoever owns the most of their disk usage along with their share thereof. There
+/// is one column for each of user and group that is requested, in the order requested.
+#[cfg(unix)]
+#[inline]
+fn format_owner(node: &Node, ctx: &Context) -> String {
+    ctx.owner
+        .iter()
+        .map(|&owner| {
+            let width = match owner {
+                Owner::User => ctx.max_user_width,
+                Owner::Group => ctx.max_group_width,
+            };
+
+            let Some(main_owner) = node.main_owner(owner) else {
+                return format!("{}      ", format_placeholder(width));
+            };
+
+            let share = main_owner.share.map_or_else(
+                || String::from("    "),
+                |share| format!("{:>3.0}%", share * 100.0),
+            );
+
+            format!("{:<width$} {share} ", main_owner.name)
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Builds the disk usage portion of the output. If both sizes are requested, the logical size
+/// comes first, followed by the physical size and optionally the ratio between them. The bytes
+/// shared through hard links come last if requested.
+#[inline]
+pub(super) fn format_size(node: &Node, ctx: &Context) -> String {
+    let format = |file_size: Option<&FileSize>| {
+        file_size.map_or_else(
+            || FileSize::placeholder(ctx),
+            |size| size.format(ctx.max_size_width, ctx.max_size_unit_width),
+        )
+    };
+
+    let size = format(node.file_size());
+
+    let mut out = if ctx.both_sizes {
+        let other_size = format(node.other_file_size());
+
+        let (logical, physical) = match ctx.disk_usage {
+            DiskUsage::Logical => (size, other_size),
+            _ => (other_size, size),
+        };
+
+        if ctx.ratio {
+            format!("{logical} {physical} {}", format_ratio(node, ctx))
+        } else {
+            format!("{logical} {physical}")
+        }
+    } else {
+        size
+    };
+
+    if ctx.shared {
+        out.push(' ');
+        out.push_str(&format(node.shared_size()));
+    }
+
+    out
+}
+
+/// Builds the ratio of physical to logical size, which is below one for sparse and compressed
+/// files and above one where block overhead dominates.
+#[inline]
+#[allow(clippy::cast_precision_loss)]
+fn format_ratio(node: &Node, ctx: &Context) -> String {
+    let bytes = |file_size: Option<&FileSize>| file_size.map(|fs| fs.bytes);
+
+    let (logical, physical) = match ctx.disk_usage {
+        DiskUsage::Logical => (bytes(node.file_size()), bytes(node.other_file_size())),
+        _ => (bytes(node.other_file_size()), bytes(node.file_size())),
+    };
+
+    match (logical, physical) {
+        (Some(logical), Some(physical)) if logical > 0 => {
+            format!("{:>RATIO_WIDTH$.2}x", physical as f64 / logical as f64)
+        }
+        _ => {
+            let placeholder = get_placeholder_style().map_or_else(
+                |_| Cow::from(PLACEHOLDER),
+                |style| Cow::from(style.paint(PLACEHOLDER).to_string()),
+            );
+
+            let padding = placeholder.len() + RATIO_WIDTH;
+
+            format!("{placeholder:>padding$}")
+        }
+    }
+}
+
+/// Builds the percentage portion of the output: the share of the parent directory, the share of
+/// the root, and a bar of the latter set against the parent directory's share of the root.
+#[inline]
+pub(super) fn format_share(node: &Node, ctx: &Context) -> String {
+    if !ctx.percent {
+        return String::new();
+    }
+
+    let share = node.share().unwrap_or(Share {
+        of_parent: 0.0,
+        of_root: 0.0,
+    });
+
+    let (filled, shaded) = bar(share);
+
+    let filled = match node.file_size().and_then(FileSize::style) {
+        Some(style) if !ctx.no_color() => style.paint(filled).to_string(),
+        _ => filled,
+    };
+
+    format!(
+        "{:>5.1}% {:>5.1}% {filled}{shaded} ",
+        share.of_parent * 100.0,
+        share.of_root * 100.0,
+    )
+}
+
+/// Builds the portion of the output with the numbers of files, directories, and links that a
+/// directory contains at any depth. Counts of zero along with anything but directories are left
+/// blank such that the columns stay aligned.
+#[inline]
+pub(super) fn format_counts(node: &Node, ctx: &Context) -> String {
+    if !ctx.counts {
+        return String::new();
+    }
+
+    let width = ctx.max_count_width;
+
+    let FileCount {
+        num_dirs,
+        num_files,
+        num_links,
+    } = node.descendants();
+
+    let count = |num, noun: &str| {
+        // Wide enough for the plural of the noun.
+        let noun_width = noun.len() + 1;
+
+        if num == 0 || !node.is_dir() {
+            return " ".repeat(width + noun_width + 1);
+        }
+
+        let plural = if num == 1 { "" } else { "s" };
+        let noun = format!("{noun}{plural}");
+
+        format!(
+            "{:>width$} {noun:<noun_width$}",
+            FileCount::format_num(num, ctx.human)
+        )
+    };
+
+    format!(
+        "{} {} {} ",
+        count(num_files, "file"),
+        count(num_dirs, "dir"),
+        count(num_links, "link"),
+    )
+}
+
+/// Splits the bar of `share` into the cells filled by the share of the root and those shaded up
+/// to the share of the root of the parent directory, which are padded to [BAR_WIDTH].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar(share: Share) -> (String, String) {
+    let eighths = (share.of_root.clamp(0.0, 1.0) * (BAR_WIDTH * 8) as f64).round() as usize;
+
+    let mut filled = std::iter::repeat(FULL_BLOCK)
+        .take(eighths / 8)
+        .collect::<String>();
+
+    if eighths % 8 != 0 {
+        filled.push(EIGHTHS[eighths % 8]);
+    }
+
+    let filled_cells = filled.chars().count();
+
+    let parent_of_root = if share.of_parent > 0.0 {
+        share.of_root / share.of_parent
+    } else {
+        0.0
+    };
+
+    let parent_cells = (parent_of_root.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+
+    let shade_cells = parent_cells.saturating_sub(filled_cells);
+    let pad_cells = BAR_WIDTH.saturating_sub(filled_cells + shade_cells);
+
+    let shaded = std::iter::repeat(SHADE)
+        .take(shade_cells)
+        .chain(std::iter::repeat(' ').take(pad_cells))
+        .collect();
+
+    (filled, shaded)
+}
+
+/// Builds the icon portion of the output.
+#[inline]
+pub(super) fn format_padded_icon(node: &Node, ctx: &Context) -> String {
+    if ctx.icons {
+        let icon = node.compute_icon(ctx.no_color());
+        let padding = icon.len() - 1;
+        format!("{icon:<padding$}")
+    } else {
+        String::new()
+    }
+}
+
+/// Builds the marker that trails the file name of a directory that wasn't fully read.
+#[inline]
+pub(super) fn format_incomplete(node: &Node, ctx: &Context) -> String {
+    if !node.is_incomplete() {
+        String::new()
+    } else if ctx.no_color() {
+        format!(" {INCOMPLETE}")
+    } else {
+        format!(" {}", Color::Yellow.bold().paint(INCOMPLETE))
+    }
+}
+
+/// Builds a numeric portion of the output.
+#[cfg(unix)]
+#[inline]
+pub(super) fn format_num(num: Option<u64>, max_width: usize, style_getter: StyleGetter) -> String {
+    let out = num
+        .map(|num| format!("{num:>max_width$}"))
+        .unwrap_or(format!("{PLACEHOLDER:>max_width$}"));
+
+    if let Ok(style) = style_getter() {
+        style.paint(out).to_string()
+    } else {
+        out
+    }
+}
+
+/// Builds a placeholder for a field of the long view that is unknown.
+#[cfg(unix)]
+#[inline]
+fn format_placeholder(width: usize) -> String {
+    let out = format!("{PLACEHOLDER:<width$}");
+
+    if let Ok(style) = styles::get_placeholder_style() {
+        style.paint(out).to_string()
+    } else {
+        out
+    }
+}
+
+#[cfg(unix)]
+#[inline]
+pub(super) fn format_datetime(datetime: Option<SystemTime>) -> String {
+    use chrono::{offset::Local, DateTime};
+
+    let out = datetime.map(DateTime::<Local>::from).map_or_else(
+        || format!("{PLACEHOLDER:>12}"),
+        |dt| format!("{:>12}", dt.format("%d %h %H:%M %g")),
+    );
+
+    if let Ok(style) = styles::get_datetime_style() {
+        style.paint(out).to_string()
+    } else {
+        out
+    }
+}
+
+#[inline]
+pub(super) fn file_name(node: &Node) -> Cow<str> {
+    node.symlink_target_file_name().map_or_else(
+        || Node::stylize(node.file_name(), node.style),
+        |target_name| {
+            let link_name = node.file_name();
+            Node::stylize_link_name(link_name, target_name, node.style)
+        },
+    )
+}
@@ -0,0 +1,24 @@
+use super::{bar, BAR_WIDTH, FULL_BLOCK};
+use crate::render::tree::node::Share;
+
+#[test]
+fn bar_of_share() {
+    let bar = |of_parent, of_root| {
+        let (filled, shaded) = bar(Share { of_parent, of_root });
+        format!("{filled}{shaded}")
+    };
+
+    assert_eq!(bar(1.0, 1.0), FULL_BLOCK.to_string().repeat(BAR_WIDTH));
+    assert_eq!(bar(0.0, 0.0), " ".repeat(BAR_WIDTH));
+    assert_eq!(
+        bar(0.5, 0.25),
+        format!(
+            "{}{}{}",
+            "\u{2588}".repeat(5),
+            "\u{2591}".repeat(5),
+            " ".repeat(10)
+        )
+    );
+    assert_eq!(bar(0.1, 0.01).chars().next(), Some('\u{258E}'));
+    assert!(bar(0.3, 0.123).chars().count() == BAR_WIDTH);
+}
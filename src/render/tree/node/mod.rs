@@ -2,10 +2,10 @@ use crate::{
     fs::inode::Inode,
     icons,
     render::{
-        context::Context,
+        context::{file::FileType, hardlink::Accounting, Context},
         disk_usage::file_size::{DiskUsage, FileSize},
         styles::get_ls_colors,
-        tree::error::Error,
+        tree::{count::FileCount, error::Error},
     },
 };
 use ansi_term::Style;
@@ -16,15 +16,17 @@ use std::{
     convert::TryFrom,
     ffi::OsStr,
     fmt::{self, Formatter},
-    fs::{FileType, Metadata},
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
 #[cfg(unix)]
-use crate::fs::{
-    permissions::{FileMode, SymbolicNotation},
-    xattr::ExtendedAttr,
+use crate::{
+    fs::{
+        permissions::{FileMode, SymbolicNotation},
+        xattr::ExtendedAttr,
+    },
+    render::context::owner::Owner,
 };
 
 /// Ordering and sorting rules for [Node].
@@ -36,90 +38,257 @@ pub mod display;
 /// Styling utilities for a [Node].
 pub mod style;
 
-/// A node of [`Tree`] that can be created from a [DirEntry]. Any filesystem I/O and
-/// relevant system calls are expected to complete after initialization. A `Node` when `Display`ed
-/// uses ANSI colors determined by the file-type and `LS_COLORS`.
+/// A node of [`Tree`] that can be created from a [DirEntry] or any other source that describes a
+/// file such as an ncdu export. Any filesystem I/O and relevant system calls are expected to
+/// complete after initialization. A `Node` when `Display`ed uses ANSI colors determined by the
+/// file-type and `LS_COLORS`.
 ///
 /// [`Tree`]: super::Tree
+#[derive(Clone)]
 pub struct Node {
-    dir_entry: DirEntry,
-    metadata: Metadata,
+    path: PathBuf,
+    depth: usize,
+    file_type: Option<FileType>,
     file_size: Option<FileSize>,
+    other_file_size: Option<FileSize>,
+    apparent_size: u64,
     style: Option<Style>,
     symlink_target: Option<PathBuf>,
     inode: Option<Inode>,
+    modified: Option<SystemTime>,
+    created: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+
+    #[cfg(unix)]
+    st_mode: Option<u32>,
+
+    #[cfg(unix)]
+    blocks: u64,
 
     #[cfg(unix)]
     has_xattrs: bool,
+
+    incomplete: bool,
+    share: Option<Share>,
+    hard_link: Option<HardLink>,
+    shared_size: Option<FileSize>,
+    descendants: FileCount,
+    collapsed: Option<FileCount>,
+
+    #[cfg(unix)]
+    ids: Option<(u32, u32)>,
+
+    #[cfg(unix)]
+    main_owners: Vec<MainOwner>,
+}
+
+/// The user or group that owns a file or, for a directory, the most of its disk usage along with
+/// the share thereof.
+#[cfg(unix)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MainOwner {
+    pub owner: Owner,
+    pub name: String,
+    pub share: Option<f64>,
+}
+
+/// Where a file with multiple hard links ranks in name order among its links within the tree, of
+/// which there are `count`. Links outside of the tree aren't known and thus never charged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HardLink {
+    pub rank: u64,
+    pub count: u64,
+}
+
+impl HardLink {
+    /// The portion of `bytes` to charge to the directory containing this link.
+    pub const fn charge(self, bytes: u64, accounting: Accounting) -> u64 {
+        let is_first = self.rank == 0;
+
+        match accounting {
+            Accounting::First if is_first => bytes,
+            Accounting::First => 0,
+            Accounting::Every => bytes,
+            Accounting::Split if is_first => bytes / self.count + bytes % self.count,
+            Accounting::Split => bytes / self.count,
+        }
+    }
+}
+
+/// The fraction of the size of the parent directory and of the root that a [Node] makes up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Share {
+    pub of_parent: f64,
+    pub of_root: f64,
+}
+
+impl Share {
+    /// Computes the [Share] of `bytes` given the sizes of the parent directory and the root.
+    pub fn new(bytes: u64, parent_bytes: u64, root_bytes: u64) -> Self {
+        let ratio = |total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                bytes as f64 / total as f64
+            }
+        };
+
+        Self {
+            of_parent: ratio(parent_bytes),
+            of_root: ratio(root_bytes),
+        }
+    }
 }
 
 impl Node {
     /// Initializes a new [Node].
     pub const fn new(
-        dir_entry: DirEntry,
-        metadata: Metadata,
+        path: PathBuf,
+        depth: usize,
+        file_type: Option<FileType>,
         file_size: Option<FileSize>,
+        apparent_size: u64,
         style: Option<Style>,
         symlink_target: Option<PathBuf>,
         inode: Option<Inode>,
+        modified: Option<SystemTime>,
+        created: Option<SystemTime>,
+        accessed: Option<SystemTime>,
 
+        #[cfg(unix)] st_mode: Option<u32>,
+        #[cfg(unix)] blocks: u64,
         #[cfg(unix)] has_xattrs: bool,
     ) -> Self {
         Self {
-            dir_entry,
-            metadata,
+            path,
+            depth,
+            file_type,
             file_size,
+            other_file_size: None,
+            apparent_size,
             style,
             symlink_target,
             inode,
+            modified,
+            created,
+            accessed,
+            #[cfg(unix)]
+            st_mode,
+            #[cfg(unix)]
+            blocks,
             #[cfg(unix)]
             has_xattrs,
+            incomplete: false,
+            share: None,
+            hard_link: None,
+            shared_size: None,
+            descendants: FileCount::new(),
+            collapsed: None,
+            #[cfg(unix)]
+            ids: None,
+            #[cfg(unix)]
+            main_owners: vec![],
         }
     }
 
+    /// Initializes a synthetic [Node] that stands in for the entries of the directory at
+    /// `parent_path` that were merged by `--collapse` as they're too small to show individually.
+    /// `collapsed` is the number of merged entries of each file-type and `descendants` that of
+    /// the entries beneath them.
+    pub fn new_collapsed(
+        parent_path: &Path,
+        depth: usize,
+        collapsed: FileCount,
+        descendants: FileCount,
+        file_size: FileSize,
+        other_file_size: Option<FileSize>,
+    ) -> Self {
+        let name = if file_size.disk_usage() == DiskUsage::Inodes {
+            format!("<{} smaller entries>", collapsed.total())
+        } else {
+            format!(
+                "<{} smaller entries, {}>",
+                collapsed.total(),
+                file_size.human_readable_display()
+            )
+        };
+
+        let mut node = Self::new(
+            parent_path.join(name),
+            depth,
+            None,
+            Some(file_size),
+            0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            #[cfg(unix)]
+            None,
+            #[cfg(unix)]
+            0,
+            #[cfg(unix)]
+            false,
+        );
+
+        node.other_file_size = other_file_size;
+        node.descendants = descendants;
+        node.collapsed = Some(collapsed);
+
+        node
+    }
+
     /// Returns a reference to `file_name`. If file is a symlink then `file_name` is the name of
-    /// the symlink not the target.
+    /// the symlink not the target. If the path terminates in `..` or is the root then the whole
+    /// path is returned.
     pub fn file_name(&self) -> &OsStr {
-        self.dir_entry.file_name()
+        self.path
+            .file_name()
+            .unwrap_or_else(|| self.path.as_os_str())
     }
 
-    pub const fn dir_entry(&self) -> &DirEntry {
-        &self.dir_entry
+    /// Get depth level of [Node].
+    pub const fn depth(&self) -> usize {
+        self.depth
     }
 
-    /// Get depth level of [Node].
-    pub fn depth(&self) -> usize {
-        self.dir_entry.depth()
+    /// Sets `depth`. Used when a [Node] was read relative to a directory other than the root.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
     }
 
-    /// Gets the number of blocks used by the underlying [DirEntry]. Returns `None` in the case of
+    /// Gets the number of blocks used by the underlying file. Returns `None` in the case of
     /// no blocks allocated like in the case of directories.
     #[cfg(unix)]
-    pub fn blocks(&self) -> Option<u64> {
-        use std::os::unix::fs::MetadataExt;
-
-        let blocks = self.metadata.blocks();
-
-        if blocks == 0 {
+    pub const fn blocks(&self) -> Option<u64> {
+        if self.blocks == 0 {
             None
         } else {
-            Some(blocks)
+            Some(self.blocks)
         }
     }
 
+    /// The logical size of the file in bytes as reported by its metadata regardless of
+    /// file-type. Unlike [`Node::file_size`] this is never aggregated.
+    pub const fn apparent_size(&self) -> u64 {
+        self.apparent_size
+    }
+
     /// Timestamp of when file was last modified.
-    pub fn modified(&self) -> Option<SystemTime> {
-        self.metadata.modified().ok()
+    pub const fn modified(&self) -> Option<SystemTime> {
+        self.modified
     }
 
     /// Timestamp of when file was created.
-    pub fn created(&self) -> Option<SystemTime> {
-        self.metadata.created().ok()
+    pub const fn created(&self) -> Option<SystemTime> {
+        self.created
     }
 
     /// Timestamp of when file was last accessed.
-    pub fn accessed(&self) -> Option<SystemTime> {
-        self.metadata.accessed().ok()
+    pub const fn accessed(&self) -> Option<SystemTime> {
+        self.accessed
     }
 
     /// Gets the underlying [Inode] of the entry.
@@ -127,24 +296,24 @@ impl Node {
         self.inode
     }
 
-    /// Returns the underlying `ino` of the [DirEntry].
+    /// Returns the underlying `ino` of the file.
     pub fn ino(&self) -> Option<u64> {
         self.inode.map(|inode| inode.ino)
     }
 
-    /// Returns the underlying `nlink` of the [DirEntry].
+    /// Returns the underlying `nlink` of the file.
     pub fn nlink(&self) -> Option<u64> {
         self.inode.map(|inode| inode.nlink)
     }
 
     /// Returns `true` if node is a directory.
-    pub fn is_dir(&self) -> bool {
-        self.file_type().map_or(false, |ft| ft.is_dir())
+    pub const fn is_dir(&self) -> bool {
+        matches!(self.file_type, Some(FileType::Dir))
     }
 
     /// Is the Node a symlink.
     pub const fn is_symlink(&self) -> bool {
-        self.symlink_target.is_some()
+        self.symlink_target.is_some() || matches!(self.file_type, Some(FileType::Link))
     }
 
     /// Path to symlink target.
@@ -157,9 +326,10 @@ impl Node {
         self.symlink_target_path().and_then(Path::file_name)
     }
 
-    /// Returns reference to underlying [FileType].
-    pub fn file_type(&self) -> Option<FileType> {
-        self.dir_entry.file_type()
+    /// Returns the [FileType] of the [Node]. Symlinks that are followed take on the file-type of
+    /// their target.
+    pub const fn file_type(&self) -> Option<FileType> {
+        self.file_type
     }
 
     /// Returns the path to the [Node]'s parent, if any.
@@ -167,10 +337,15 @@ impl Node {
         self.path().parent()
     }
 
-    /// Returns a reference to `path`. If the underlying [DirEntry] is a symlink then the path of
-    /// the symlink shall be returned.
+    /// Returns a reference to `path`. If the underlying file is a symlink then the path of the
+    /// symlink shall be returned.
     pub fn path(&self) -> &Path {
-        self.dir_entry.path()
+        &self.path
+    }
+
+    /// The `LS_COLORS`-derived [Style] of the [Node], if any.
+    pub const fn style(&self) -> Option<Style> {
+        self.style
     }
 
     /// Gets 'file_size'.
@@ -183,17 +358,177 @@ impl Node {
         self.file_size = Some(size);
     }
 
+    /// Marks the [Node] as a directory whose contents weren't fully read because traversal was
+    /// interrupted.
+    pub fn mark_incomplete(&mut self) {
+        self.incomplete = true;
+    }
+
+    /// Were the contents of the directory not fully read?
+    pub const fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+
+    /// Is the [Node] a directory that's to be removed for being empty, given whether anything
+    /// beneath it is left? Directories that weren't fully read may well not be empty.
+    pub fn is_prunable(&self, ctx: &Context, is_occupied: bool) -> bool {
+        self.is_dir()
+            && !is_occupied
+            && !self.incomplete
+            && (ctx.prune || ctx.file_type != Some(FileType::Dir))
+    }
+
+    /// Gets `share`, which is only computed if requested via `--percent`.
+    pub const fn share(&self) -> Option<Share> {
+        self.share
+    }
+
+    /// Sets `share`.
+    pub fn set_share(&mut self, share: Share) {
+        self.share = Some(share);
+    }
+
+    /// Gets `other_file_size`, the size of the other [DiskUsage] than the one in use which is
+    /// only computed if requested via `--both-sizes`.
+    pub const fn other_file_size(&self) -> Option<&FileSize> {
+        self.other_file_size.as_ref()
+    }
+
+    /// Sets `other_file_size`.
+    pub fn set_other_file_size(&mut self, size: FileSize) {
+        self.other_file_size = Some(size);
+    }
+
+    /// Gets `hard_link`, which is only known for files that have multiple hard links.
+    pub const fn hard_link(&self) -> Option<HardLink> {
+        self.hard_link
+    }
+
+    /// Sets `hard_link`, which is only known for files that have multiple hard links.
+    pub fn set_hard_link(&mut self, hard_link: HardLink) {
+        self.hard_link = Some(hard_link);
+    }
+
+    /// The number of bytes of `file_size` to charge to the parent directory, which depends on
+    /// how files with multiple hard links are accounted for, see [HardLink::charge].
+    pub fn charged_bytes(&self, accounting: Accounting) -> u64 {
+        self.charge(self.file_size(), accounting)
+    }
+
+    /// The number of bytes of `other_file_size` to charge to the parent directory, see
+    /// [Self::charged_bytes].
+    pub fn charged_other_bytes(&self, accounting: Accounting) -> u64 {
+        self.charge(self.other_file_size(), accounting)
+    }
+
+    /// The portion of `file_size` that is charged to the parent directory.
+    fn charge(&self, file_size: Option<&FileSize>, accounting: Accounting) -> u64 {
+        let bytes = file_size.map_or(0, |fs| fs.bytes);
+
+        self.hard_link
+            .map_or(bytes, |hard_link| hard_link.charge(bytes, accounting))
+    }
+
+    /// Is the [Node] a file with multiple hard links?
+    pub fn is_hard_linked(&self) -> bool {
+        !self.is_dir() && self.inode.map_or(false, |inode| inode.nlink > 1)
+    }
+
+    /// Gets `shared_size`, the number of bytes shared through hard links with entries outside of
+    /// the [Node], which is only computed if requested via `--shared`.
+    pub const fn shared_size(&self) -> Option<&FileSize> {
+        self.shared_size.as_ref()
+    }
+
+    /// Sets `shared_size`.
+    pub fn set_shared_size(&mut self, size: FileSize) {
+        self.shared_size = Some(size);
+    }
+
+    /// Gets `descendants`, the number of entries of each file-type beneath the [Node] at any
+    /// depth.
+    pub const fn descendants(&self) -> FileCount {
+        self.descendants
+    }
+
+    /// Sets `descendants`.
+    pub fn set_descendants(&mut self, descendants: FileCount) {
+        self.descendants = descendants;
+    }
+
+    /// Gets `collapsed`, the number of entries of each file-type that a synthetic [Node] made by
+    /// `--collapse` stands in for.
+    pub const fn collapsed(&self) -> Option<FileCount> {
+        self.collapsed
+    }
+
+    /// Is the [Node] a synthetic one standing in for entries merged by `--collapse`?
+    pub const fn is_collapsed(&self) -> bool {
+        self.collapsed.is_some()
+    }
+
+    /// Unsets `file_size` and `other_file_size`.
+    pub fn clear_file_size(&mut self) {
+        self.file_size = None;
+        self.other_file_size = None;
+    }
+
     /// Attempts to return an instance of [FileMode] for the display of symbolic permissions.
+    /// Returns `None` if the mode is not known or not valid.
+    #[cfg(unix)]
+    pub fn mode(&self) -> Option<FileMode> {
+        use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+        self.st_mode
+            .map(Permissions::from_mode)
+            .and_then(|permissions| permissions.try_mode_symbolic_notation().ok())
+    }
+
+    /// The raw `st_mode` of the underlying file including the file-type bits, if known.
     #[cfg(unix)]
-    pub fn mode(&self) -> Result<FileMode, Error> {
-        let permissions = self.metadata.permissions();
-        let file_mode = permissions.try_mode_symbolic_notation()?;
-        Ok(file_mode)
+    pub const fn st_mode(&self) -> Option<u32> {
+        self.st_mode
+    }
+
+    /// Sets the `uid` and `gid` of the user and group that own the underlying file.
+    #[cfg(unix)]
+    pub fn set_ids(&mut self, uid: u32, gid: u32) {
+        self.ids = Some((uid, gid));
+    }
+
+    /// The `uid` and `gid` of the user and group that own the underlying file, if known.
+    #[cfg(unix)]
+    pub const fn ids(&self) -> Option<(u32, u32)> {
+        self.ids
+    }
+
+    /// The id of the user or group that owns the underlying file, if known.
+    #[cfg(unix)]
+    pub fn owner_id(&self, owner: Owner) -> Option<u32> {
+        self.ids.map(|(uid, gid)| match owner {
+            Owner::User => uid,
+            Owner::Group => gid,
+        })
+    }
+
+    /// Gets the [MainOwner] by user or by group, which is only computed if requested via
+    /// `--owner`.
+    #[cfg(unix)]
+    pub fn main_owner(&self, owner: Owner) -> Option<&MainOwner> {
+        self.main_owners.iter().find(|main| main.owner == owner)
+    }
+
+    /// Sets the [MainOwner] of its kind, replacing the previous one if any.
+    #[cfg(unix)]
+    pub fn set_main_owner(&mut self, main_owner: MainOwner) {
+        self.main_owners
+            .retain(|main| main.owner != main_owner.owner);
+        self.main_owners.push(main_owner);
     }
 
     /// Whether or not [Node] has extended attributes.
     #[cfg(unix)]
-    const fn has_xattrs(&self) -> bool {
+    pub const fn has_xattrs(&self) -> bool {
         self.has_xattrs
     }
 
@@ -212,9 +547,14 @@ impl Node {
     /// See [icons::compute].
     fn compute_icon(&self, no_color: bool) -> Cow<'static, str> {
         if no_color {
-            icons::compute(self.dir_entry(), self.symlink_target_path())
+            icons::compute(self.path(), self.file_type, self.symlink_target_path())
         } else {
-            icons::compute_with_color(self.dir_entry(), self.symlink_target_path(), self.style)
+            icons::compute_with_color(
+                self.path(),
+                self.file_type,
+                self.symlink_target_path(),
+                self.style,
+            )
         }
     }
 }
@@ -240,17 +580,38 @@ impl TryFrom<(DirEntry, &Context)> for Node {
 
         let file_type = dir_entry.file_type();
 
-        let mut file_size = match file_type {
-            Some(ref ft) if ft.is_file() && !ctx.suppress_size => match ctx.disk_usage {
+        let is_sized = |disk_usage| match file_type {
+            _ if ctx.suppress_size => false,
+            _ if disk_usage == DiskUsage::Inodes => true,
+            Some(ref ft) if ft.is_file() => true,
+            Some(_) => ctx.own_blocks && disk_usage == DiskUsage::Physical,
+            None => false,
+        };
+
+        let sized = |disk_usage| {
+            if !is_sized(disk_usage) {
+                return None;
+            }
+
+            let mut file_size = match disk_usage {
                 DiskUsage::Logical => Some(FileSize::logical(&metadata, ctx.unit, ctx.human)),
                 DiskUsage::Physical => FileSize::physical(path, &metadata, ctx.unit, ctx.human),
-            },
-            _ => None,
+                DiskUsage::Inodes => Some(FileSize::inode(ctx.unit, ctx.human)),
+            };
+
+            if let Some(ref mut fs) = file_size {
+                fs.precompute_unpadded_display();
+            }
+
+            file_size
         };
 
-        if let Some(ref mut fs) = file_size {
-            fs.precompute_unpadded_display();
-        }
+        let file_size = sized(ctx.disk_usage);
+
+        let other_file_size = ctx
+            .both_sizes
+            .then(|| sized(ctx.disk_usage.other()))
+            .flatten();
 
         let inode = Inode::try_from(&metadata).ok();
 
@@ -261,15 +622,44 @@ impl TryFrom<(DirEntry, &Context)> for Node {
             false
         };
 
-        Ok(Self::new(
-            dir_entry,
-            metadata,
+        #[cfg(unix)]
+        let (st_mode, blocks, ids) = {
+            use std::os::unix::fs::MetadataExt;
+
+            (
+                Some(metadata.mode()),
+                metadata.blocks(),
+                (metadata.uid(), metadata.gid()),
+            )
+        };
+
+        let mut node = Self::new(
+            dir_entry.path().to_path_buf(),
+            dir_entry.depth(),
+            file_type.map(FileType::from),
             file_size,
+            metadata.len(),
             style,
             link_target,
             inode,
+            metadata.modified().ok(),
+            metadata.created().ok(),
+            metadata.accessed().ok(),
+            #[cfg(unix)]
+            st_mode,
+            #[cfg(unix)]
+            blocks,
             #[cfg(unix)]
             has_xattrs,
-        ))
+        );
+
+        if let Some(size) = other_file_size {
+            node.set_other_file_size(size);
+        }
+
+        #[cfg(unix)]
+        node.set_ids(ids.0, ids.1);
+
+        Ok(node)
     }
 }
@@ -1,6 +1,8 @@
 use super::Node;
+use crate::render::{context::file::FileType, styles::get_ls_colors};
 use ansi_term::{Color, Style};
-use std::{borrow::Cow, ffi::OsStr};
+use lscolors::{Indicator, Style as LS_Style};
+use std::{borrow::Cow, ffi::OsStr, path::Path};
 
 #[cfg(unix)]
 use crate::{
@@ -9,6 +11,22 @@ use crate::{
 };
 
 impl Node {
+    /// Determines the `LS_COLORS` [Style] of a file from its path and [FileType] alone, for when
+    /// its `Metadata` isn't available such as when reading in an export or a snapshot.
+    pub fn style_for_path(path: &Path, file_type: Option<FileType>) -> Option<Style> {
+        get_ls_colors().ok().and_then(|ls_colors| {
+            let style = match file_type {
+                Some(FileType::Dir) => ls_colors.style_for_indicator(Indicator::Directory),
+                Some(FileType::Link) => ls_colors.style_for_indicator(Indicator::SymbolicLink),
+                _ => ls_colors.style_for_path_with_metadata(path, None),
+            };
+
+            style
+                .map(LS_Style::to_ansi_term_style)
+                .or_else(|| Some(Style::default()))
+        })
+    }
+
     /// Stylizes input, `entity` based on `LS_COLORS`. If `style` is `None` then the entity is
     /// returned unmodified.
     pub(super) fn stylize(file_name: &OsStr, style: Option<Style>) -> Cow<'_, str> {
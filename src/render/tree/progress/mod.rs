@@ -0,0 +1,170 @@
+use super::node::Node;
+use crate::render::{context::Context, disk_usage::file_size::FileSize};
+use crossterm::{
+    cursor::MoveToColumn,
+    queue,
+    terminal::{self, Clear, ClearType},
+};
+use std::{
+    io::{self, Stderr, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Unit tests for the progress line.
+#[cfg(test)]
+mod test;
+
+/// Minimum amount of time between redraws of the progress line. Also the longest the line goes
+/// without being redrawn while traversal is ongoing.
+pub const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Width to assume if that of the terminal can't be determined.
+const FALLBACK_WIDTH: usize = 80;
+
+/// Progress of the traversal reported on a single, continuously redrawn line on stderr so that
+/// long traversals don't appear to hang. The line is cleared when [Progress] is dropped. Tests
+/// draw to a buffer in place of stderr.
+pub struct Progress<'a, W: Write = Stderr> {
+    ctx: &'a Context,
+    out: W,
+    started: Instant,
+    last_drawn: Instant,
+    entries: u64,
+    bytes: u64,
+    dir: PathBuf,
+    is_drawn: bool,
+}
+
+impl<'a> Progress<'a> {
+    /// Returns `None` if stderr isn't a tty.
+    pub fn init(ctx: &'a Context) -> Option<Self> {
+        if !ctx.stderr_is_tty {
+            return None;
+        }
+
+        Some(Progress::new(ctx, io::stderr()))
+    }
+}
+
+impl<'a, W: Write> Progress<'a, W> {
+    /// Reports progress to `out` whether it's a tty or not.
+    fn new(ctx: &'a Context, out: W) -> Self {
+        let now = Instant::now();
+
+        Self {
+            ctx,
+            out,
+            started: now,
+            last_drawn: now,
+            entries: 0,
+            bytes: 0,
+            dir: ctx.dir_canonical(),
+            is_drawn: false,
+        }
+    }
+
+    /// Accounts for a freshly visited [Node] and redraws the progress line if it hasn't been
+    /// for at least [INTERVAL].
+    pub fn update(&mut self, node: &Node) {
+        self.entries += 1;
+
+        if let Some(file_size) = node.file_size() {
+            self.bytes += file_size.bytes;
+        }
+
+        if !self.is_due(Instant::now()) {
+            return;
+        }
+
+        let dir = if node.is_dir() {
+            Some(node.path())
+        } else {
+            node.parent_path()
+        };
+
+        dir.unwrap_or_else(|| node.path()).clone_into(&mut self.dir);
+        self.redraw();
+    }
+
+    /// Redraws the progress line if it hasn't been for at least [INTERVAL] so that the elapsed
+    /// time keeps ticking while no new entries are coming in, e.g. while a slow directory is
+    /// being read.
+    pub fn tick(&mut self) {
+        if self.is_due(Instant::now()) {
+            self.redraw();
+        }
+    }
+
+    /// Has it been at least [INTERVAL] since the progress line was last drawn?
+    fn is_due(&self, now: Instant) -> bool {
+        now.duration_since(self.last_drawn) >= INTERVAL
+    }
+
+    /// Draws the progress line, which is best-effort; failing to report progress shouldn't fail
+    /// the traversal.
+    fn redraw(&mut self) {
+        self.last_drawn = Instant::now();
+
+        let width = terminal::size().map_or(FALLBACK_WIDTH, |(cols, _)| usize::from(cols));
+        let line = self.line(width);
+
+        let out = &mut self.out;
+
+        let drawn = queue!(out, MoveToColumn(0), Clear(ClearType::CurrentLine))
+            .and_then(|()| write!(out, "{line}"))
+            .and_then(|()| out.flush());
+
+        self.is_drawn |= drawn.is_ok();
+    }
+
+    /// The progress line for a terminal `width` columns wide. The directory currently being read
+    /// is shortened from the left such that the line never wraps.
+    fn line(&self, width: usize) -> String {
+        let ctx = self.ctx;
+
+        let bytes = FileSize::new(self.bytes, ctx.disk_usage, true, ctx.unit);
+
+        let status = format!(
+            "{:.1}s  {} entries  {}  ",
+            self.started.elapsed().as_secs_f64(),
+            self.entries,
+            bytes.human_readable_display()
+        );
+
+        let available = width.saturating_sub(status.chars().count() + 1);
+
+        format!("{status}{}", shorten(&self.dir, available))
+    }
+}
+
+/// Shortens the display of `dir` from the left, marking the truncation with an ellipsis, such
+/// that it's at most `available` characters long.
+fn shorten(dir: &Path, available: usize) -> String {
+    let dir = dir.display().to_string();
+    let dir_len = dir.chars().count();
+
+    if dir_len <= available {
+        return dir;
+    }
+
+    if available == 0 {
+        return String::new();
+    }
+
+    let tail = dir
+        .chars()
+        .skip(dir_len + 1 - available)
+        .collect::<String>();
+
+    format!("\u{2026}{tail}")
+}
+
+impl<W: Write> Drop for Progress<'_, W> {
+    fn drop(&mut self) {
+        if self.is_drawn {
+            let _ = queue!(self.out, MoveToColumn(0), Clear(ClearType::CurrentLine));
+            let _ = self.out.flush();
+        }
+    }
+}
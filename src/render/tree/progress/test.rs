@@ -0,0 +1,83 @@
+use super::{shorten, Progress, INTERVAL};
+use crate::render::context::Context;
+use clap::Parser;
+use std::{path::Path, time::Instant};
+
+fn context() -> Context {
+    let mut ctx =
+        Context::try_parse_from(["erd", "--disk-usage", "logical", "tests/data"]).unwrap();
+    ctx.stderr_is_tty = true;
+    ctx
+}
+
+#[test]
+fn shorten_fits() {
+    assert_eq!(shorten(Path::new("/a/b/c"), 6), "/a/b/c");
+    assert_eq!(shorten(Path::new("/a/b/c"), 80), "/a/b/c");
+}
+
+#[test]
+fn shorten_from_the_left() {
+    assert_eq!(shorten(Path::new("/a/b/c"), 4), "\u{2026}b/c");
+    assert_eq!(shorten(Path::new("/a/b/c"), 1), "\u{2026}");
+    assert_eq!(shorten(Path::new("/a/b/c"), 0), "");
+}
+
+#[test]
+fn only_on_tty() {
+    let mut ctx = context();
+    ctx.stderr_is_tty = false;
+
+    assert!(Progress::init(&ctx).is_none());
+}
+
+#[test]
+fn line_never_wraps() {
+    let ctx = context();
+    let mut progress = Progress::new(&ctx, vec![]);
+    progress.dir = Path::new("/").join("x".repeat(200));
+
+    for width in [0, 10, 40, 80, 300] {
+        let line = progress.line(width);
+
+        assert!(line.contains("s  0 entries  0 B  "), "{line}");
+        assert!(width < 25 || line.chars().count() < width, "{line}");
+    }
+}
+
+#[test]
+fn redraws_on_a_timer() {
+    let ctx = context();
+    let mut progress = Progress::new(&ctx, vec![]);
+    let now = Instant::now();
+
+    assert!(!progress.is_due(progress.last_drawn));
+    assert!(progress.is_due(progress.last_drawn + INTERVAL));
+
+    progress.tick();
+    assert!(progress.out.is_empty());
+
+    // Backdate the last draw as if traversal had been stuck on a slow directory.
+    progress.last_drawn = now.checked_sub(INTERVAL).unwrap();
+    progress.tick();
+
+    assert!(progress.last_drawn >= now);
+    assert!(!progress.is_due(now));
+
+    let drawn = String::from_utf8_lossy(&progress.out);
+    assert!(drawn.contains("s  0 entries  0 B  "), "{drawn}");
+}
+
+#[test]
+fn cleared_when_dropped() {
+    let ctx = context();
+    let mut out = vec![];
+
+    let mut progress = Progress::new(&ctx, &mut out);
+    progress.last_drawn = Instant::now().checked_sub(INTERVAL).unwrap();
+    progress.tick();
+    drop(progress);
+
+    let drawn = String::from_utf8_lossy(&out);
+    assert!(drawn.ends_with("\u{1b}[1G\u{1b}[2K"), "{drawn:?}");
+}
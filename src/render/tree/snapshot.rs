@@ -0,0 +1,298 @@
+use super::{error::Error, node::Node, Result};
+use crate::{
+    fs::inode::Inode,
+    render::{
+        context::{file::FileType, Context},
+        disk_usage::file_size::{DiskUsage, FileSize},
+    },
+};
+use indextree::{Arena, NodeId};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Leading bytes of every snapshot so that arbitrary files aren't mistaken for one.
+const MAGIC: &[u8; 8] = b"ERDSNAP\0";
+
+/// Bumped whenever the layout of [Snapshot] changes in an incompatible way.
+const FORMAT_VERSION: u32 = 2;
+
+/// Everything that is needed to reconstruct a [`Tree`] without touching the filesystem.
+///
+/// [`Tree`]: super::Tree
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    header: Header,
+    nodes: Vec<SnapshotNode>,
+}
+
+/// Describes how and when the snapshot was taken.
+#[derive(Serialize, Deserialize)]
+pub struct Header {
+    /// Version of erdtree that took the snapshot.
+    pub version: String,
+
+    /// Canonical path of the root directory that was traversed.
+    pub root: PathBuf,
+
+    /// When the snapshot was taken.
+    pub taken: SystemTime,
+
+    /// Which disk usage file sizes were computed with.
+    pub disk_usage: DiskUsage,
+
+    /// Whether symlinks were followed.
+    pub follow: bool,
+
+    /// Whether hidden files were traversed.
+    pub hidden: bool,
+
+    /// Whether `.gitignore` files were disregarded.
+    pub no_ignore: bool,
+}
+
+/// Plain attributes of a single [Node]. Directories don't store a size as it's derived from their
+/// contents once the [`Tree`] is assembled.
+///
+/// [`Tree`]: super::Tree
+#[derive(Serialize, Deserialize)]
+struct SnapshotNode {
+    path: PathBuf,
+    depth: usize,
+    file_type: Option<FileType>,
+    size: Option<u64>,
+    apparent_size: u64,
+    symlink_target: Option<PathBuf>,
+    inode: Option<Inode>,
+    modified: Option<SystemTime>,
+    created: Option<SystemTime>,
+    accessed: Option<SystemTime>,
+    st_mode: Option<u32>,
+    blocks: u64,
+    has_xattrs: bool,
+    ids: Option<(u32, u32)>,
+}
+
+/// Writes all of the nodes of the assembled tree in pre-order to `path`.
+pub fn save(path: &Path, arena: &Arena<Node>, root_id: NodeId, ctx: &Context) -> Result<()> {
+    let header = Header {
+        version: String::from(env!("CARGO_PKG_VERSION")),
+        root: ctx.dir_canonical(),
+        taken: SystemTime::now(),
+        disk_usage: ctx.disk_usage,
+        follow: ctx.follow,
+        hidden: ctx.hidden,
+        no_ignore: ctx.no_ignore,
+    };
+
+    let nodes = root_id
+        .descendants(arena)
+        .map(|node_id| SnapshotNode::from(arena[node_id].get()))
+        .collect();
+
+    let snapshot = Snapshot { header, nodes };
+
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+
+    bincode::serialize_into(&mut writer, &snapshot).map_err(|e| Error::Snapshot(e.to_string()))?;
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Reads a snapshot from `path` and returns its [Header] along with a [Node] for every entry in
+/// pre-order. Fails if `--hidden` or `--no-ignore` ask for entries that the snapshot was taken
+/// without. As with traversal, entries that don't survive `--hidden` or `--pattern` are
+/// dropped along with their descendants. Sizes are recomputed according to `--disk-usage`.
+pub fn load(path: &Path, ctx: &Context) -> Result<(Header, Vec<Node>)> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut magic = [0; MAGIC.len()];
+    let mut version = [0; 4];
+
+    if reader.read_exact(&mut magic).is_err() || &magic != MAGIC {
+        return Err(Error::Snapshot(format!(
+            "{} is not a snapshot",
+            path.display()
+        )));
+    }
+
+    reader.read_exact(&mut version)?;
+
+    let version = u32::from_le_bytes(version);
+
+    if version != FORMAT_VERSION {
+        return Err(Error::Snapshot(format!(
+            "unsupported snapshot format version {version}"
+        )));
+    }
+
+    let snapshot: Snapshot =
+        bincode::deserialize_from(reader).map_err(|e| Error::Snapshot(e.to_string()))?;
+
+    let Snapshot { header, nodes } = snapshot;
+
+    // Entries that weren't traversed can't be shown no matter what.
+    let missing = [
+        ("--hidden", ctx.hidden && !header.hidden),
+        ("--no-ignore", ctx.no_ignore && !header.no_ignore),
+    ];
+
+    if let Some((flag, _)) = missing.into_iter().find(|(_, is_missing)| *is_missing) {
+        return Err(Error::Snapshot(format!(
+            "{} was taken without '{flag}' and lacks the entries it would show",
+            path.display()
+        )));
+    }
+
+    let predicate = ctx.pattern_predicate()?;
+
+    // Depth of the most recently dropped entry whose descendants must be dropped too.
+    let mut dropped_depth = None;
+
+    let mut out = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        match dropped_depth {
+            Some(depth) if node.depth > depth => continue,
+            _ => dropped_depth = None,
+        }
+
+        if node.depth > 0 {
+            let is_hidden = node
+                .path
+                .file_name()
+                .map_or(false, |name| name.to_string_lossy().starts_with('.'));
+
+            let is_match = predicate
+                .as_ref()
+                .map_or(true, |predicate| predicate(&node.path, node.file_type));
+
+            if (is_hidden && !ctx.hidden) || !is_match {
+                dropped_depth = Some(node.depth);
+                continue;
+            }
+        }
+
+        out.push(node.into_node(&header, ctx));
+    }
+
+    Ok((header, out))
+}
+
+impl From<&Node> for SnapshotNode {
+    fn from(node: &Node) -> Self {
+        let size = if node.is_dir() {
+            None
+        } else {
+            node.file_size().map(|fs| fs.bytes)
+        };
+
+        Self {
+            path: node.path().to_path_buf(),
+            depth: node.depth(),
+            file_type: node.file_type(),
+            size,
+            apparent_size: node.apparent_size(),
+            symlink_target: node.symlink_target_path().map(Path::to_path_buf),
+            inode: node.inode(),
+            modified: node.modified(),
+            created: node.created(),
+            accessed: node.accessed(),
+            #[cfg(unix)]
+            st_mode: node.st_mode(),
+            #[cfg(not(unix))]
+            st_mode: None,
+            #[cfg(unix)]
+            blocks: node.blocks().unwrap_or(0),
+            #[cfg(not(unix))]
+            blocks: 0,
+            #[cfg(unix)]
+            has_xattrs: node.has_xattrs(),
+            #[cfg(not(unix))]
+            has_xattrs: false,
+            #[cfg(unix)]
+            ids: node.ids(),
+            #[cfg(not(unix))]
+            ids: None,
+        }
+    }
+}
+
+impl SnapshotNode {
+    /// Converts back into a [Node] sized according to the current [Context]. If the snapshot
+    /// was taken with a different `--disk-usage` then the size is derived from what's at hand.
+    fn into_node(self, header: &Header, ctx: &Context) -> Node {
+        let sized = |disk_usage| {
+            let is_file = self.file_type == Some(FileType::File);
+            let is_sized = is_file
+                || disk_usage == DiskUsage::Inodes
+                || (ctx.own_blocks && disk_usage == DiskUsage::Physical);
+
+            let bytes = match disk_usage {
+                _ if !is_sized => None,
+                DiskUsage::Inodes => Some(1),
+                // The size of directories isn't saved as it's derived from their contents.
+                DiskUsage::Physical if !is_file => cfg!(unix).then_some(self.blocks * 512),
+                DiskUsage::Logical => self.size.map(|_| self.apparent_size),
+                DiskUsage::Physical if cfg!(unix) && header.disk_usage != DiskUsage::Physical => {
+                    self.size.map(|_| self.blocks * 512)
+                }
+                DiskUsage::Physical if header.disk_usage != DiskUsage::Physical => {
+                    self.size.map(|_| self.apparent_size)
+                }
+                DiskUsage::Physical => self.size,
+            };
+
+            bytes.filter(|_| !ctx.suppress_size).map(|bytes| {
+                let mut file_size = FileSize::new(bytes, disk_usage, ctx.human, ctx.unit);
+                file_size.precompute_unpadded_display();
+                file_size
+            })
+        };
+
+        let file_size = sized(ctx.disk_usage);
+        let other_file_size = sized(ctx.disk_usage.other()).filter(|_| ctx.both_sizes);
+
+        let style = Node::style_for_path(&self.path, self.file_type);
+
+        let mut node = Node::new(
+            self.path,
+            self.depth,
+            self.file_type,
+            file_size,
+            self.apparent_size,
+            style,
+            self.symlink_target,
+            self.inode,
+            self.modified,
+            self.created,
+            self.accessed,
+            #[cfg(unix)]
+            self.st_mode,
+            #[cfg(unix)]
+            self.blocks,
+            #[cfg(unix)]
+            self.has_xattrs,
+        );
+
+        if let Some(size) = other_file_size {
+            node.set_other_file_size(size);
+        }
+
+        #[cfg(unix)]
+        if let Some((uid, gid)) = self.ids {
+            node.set_ids(uid, gid);
+        }
+
+        node
+    }
+}
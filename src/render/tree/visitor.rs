@@ -1,32 +1,81 @@
-use std::sync::mpsc::Sender;
+use std::{mem, path::PathBuf, sync::mpsc::Sender};
 
-use super::{Context, Node};
+use super::{
+    interrupt::{self, Listings, Outstanding},
+    Context, Node,
+};
 use ignore::{DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkState};
 
 pub enum TraversalState {
     Ongoing(Node),
+
+    /// The number of entries that were read from a directory, which may arrive before or after.
+    Listed(PathBuf, usize),
+
+    /// An entry that was read from its directory but couldn't be turned into a [Node].
+    Unreadable(PathBuf),
+
     Done,
 }
 
 pub struct Branch<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    outstanding: Outstanding,
+    listings: Listings,
+
+    /// Whether to report [TraversalState::Listed] and [TraversalState::Unreadable] such that the
+    /// receiver can tell when a directory is complete.
+    report_listings: bool,
 }
 
 pub struct BranchVisitorBuilder<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    outstanding: Outstanding,
+    report_listings: bool,
 }
 
 impl<'a> BranchVisitorBuilder<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(
+        ctx: &'a Context,
+        tx: Sender<TraversalState>,
+        outstanding: Outstanding,
+        report_listings: bool,
+    ) -> Self {
+        Self {
+            ctx,
+            tx,
+            outstanding,
+            report_listings,
+        }
     }
 }
 
 impl<'a> Branch<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(
+        ctx: &'a Context,
+        tx: Sender<TraversalState>,
+        outstanding: Outstanding,
+        report_listings: bool,
+    ) -> Self {
+        Self {
+            ctx,
+            tx,
+            outstanding,
+            listings: Listings::default(),
+            report_listings,
+        }
+    }
+
+    /// Closes the listing of the directory that was being read on the current thread, if any,
+    /// see [Listings::close].
+    fn close_listing(&mut self) {
+        let listed = self.listings.close();
+
+        if let Some((dir, count)) = listed.filter(|_| self.report_listings) {
+            let _ = self.tx.send(TraversalState::Listed(dir, count));
+        }
     }
 }
 
@@ -39,22 +88,58 @@ impl From<Node> for TraversalState {
 impl ParallelVisitor for Branch<'_> {
     fn visit(&mut self, entry: Result<DirEntry, IgnoreError>) -> WalkState {
         let Ok(dir_entry) = entry else {
+            if interrupt::is_interrupted() {
+                self.listings.truncate();
+                return WalkState::Quit;
+            }
+
             return WalkState::Skip;
         };
 
-        match Node::try_from((dir_entry, self.ctx)) {
-            Ok(node) => {
-                self.tx.send(TraversalState::from(node)).unwrap();
-                WalkState::Continue
+        self.close_listing();
+
+        if interrupt::is_interrupted() {
+            return WalkState::Quit;
+        }
+
+        let path = self.report_listings.then(|| dir_entry.path().to_path_buf());
+
+        let Ok(node) = Node::try_from((dir_entry, self.ctx)) else {
+            if let Some(path) = path {
+                let _ = self.tx.send(TraversalState::Unreadable(path));
             }
-            _ => WalkState::Skip,
+
+            return WalkState::Skip;
+        };
+
+        if node.is_dir() {
+            self.listings.open(node.path());
         }
+
+        // The receiver is gone if it ran into an error.
+        if self.tx.send(TraversalState::from(node)).is_err() {
+            return WalkState::Quit;
+        }
+
+        WalkState::Continue
+    }
+}
+
+impl Drop for Branch<'_> {
+    fn drop(&mut self) {
+        self.close_listing();
+        self.outstanding.merge(mem::take(&mut self.listings));
     }
 }
 
 impl<'s> ParallelVisitorBuilder<'s> for BranchVisitorBuilder<'s> {
     fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
-        let visitor = Branch::new(self.ctx, self.tx.clone());
+        let visitor = Branch::new(
+            self.ctx,
+            self.tx.clone(),
+            Outstanding::clone(&self.outstanding),
+            self.report_listings,
+        );
         Box::new(visitor)
     }
 }
@@ -0,0 +1,452 @@
+use super::{
+    count::FileCount,
+    display::TreeVariant,
+    error::Error,
+    node::{HardLink, Node},
+    walk_builder, Result, Tree,
+};
+use crate::render::{
+    context::{hardlink::Accounting, output::ColumnProperties, sort::SortType},
+    disk_usage::file_size::FileSize,
+};
+use indextree::NodeId;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    ffi::{OsStr, OsString},
+    mem,
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+};
+
+/// Unit tests for bringing the [Tree] up to date.
+#[cfg(test)]
+mod test;
+
+/// Identifies a file irrespective of its number of links, which may have changed.
+type FileId = (u64, u64);
+
+impl<T> Tree<T>
+where
+    T: TreeVariant,
+{
+    /// Brings the [Tree] up to date after the filesystem changed at `paths`. Rather than
+    /// traversing everything anew, only the directories that immediately contain the changed paths
+    /// are read again. Newly created directories are traversed in full. The change in size of
+    /// each directory that was read again is then carried up its ancestor chain.
+    ///
+    /// Changes are made to the [Tree] as it was assembled, before anything was filtered out,
+    /// which is then finalized anew. A [Tree] that wasn't initialized in `--watch` mode is taken
+    /// to have had nothing filtered out.
+    pub fn refresh<I, P>(&mut self, paths: I) -> Result<()>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        if let Some(assembled) = self.assembled.take() {
+            self.arena = assembled;
+        }
+
+        let mut dirty = paths
+            .into_iter()
+            .filter_map(|path| path.as_ref().parent().and_then(|p| self.nearest_dir(p)))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // Deepest first such that a parent that is read again accounts for the updated sizes of
+        // its children.
+        dirty.sort_by_key(|dir_id| Reverse(self.arena[*dir_id].get().depth()));
+
+        let mut relinked = HashSet::new();
+        let mut resized = HashSet::new();
+
+        for dir_id in dirty {
+            if dir_id.is_removed(&self.arena) {
+                continue;
+            }
+
+            let old_sizes = sizes_of(self.arena[dir_id].get());
+            let new_sizes = self.reread_dir(dir_id, &mut relinked)?;
+
+            resized.insert(dir_id);
+            self.resize_ancestors(dir_id, old_sizes, new_sizes, &mut resized);
+        }
+
+        // Only the charges of files whose links changed are affected.
+        if self.ctx.hardlinks != Accounting::Every && !relinked.is_empty() {
+            self.rerank_hard_links(&relinked, &mut resized);
+        }
+
+        let node_comparator = super::node::cmp::comparator(&self.ctx);
+
+        for dir_id in resized {
+            if !dir_id.is_removed(&self.arena) {
+                self.sort_children(dir_id, &node_comparator);
+            }
+        }
+
+        self.recount_descendants(self.root_id);
+
+        if matches!(self.ctx.sort, SortType::Count | SortType::CountRev) {
+            self.resort();
+        }
+
+        let mut column_properties = ColumnProperties::from(&self.ctx);
+        let mut arena = self.arena.clone();
+
+        Self::finalize(&mut arena, self.root_id, &mut column_properties, &self.ctx);
+
+        for node_id in self.root_id.descendants(&arena) {
+            let node = arena[node_id].get();
+
+            #[cfg(unix)]
+            Self::update_column_properties(&mut column_properties, node, self.ctx.long);
+
+            #[cfg(not(unix))]
+            Self::update_column_properties(&mut column_properties, node);
+        }
+
+        self.assembled = Some(mem::replace(&mut self.arena, arena));
+
+        self.ctx.update_column_properties(&column_properties);
+
+        if self.ctx.truncate {
+            self.ctx.set_window_width();
+        }
+
+        Ok(())
+    }
+
+    /// Counts the descendants of the directory at `dir_id` and of all directories below it anew
+    /// as entries that were read again no longer carry their counts.
+    fn recount_descendants(&mut self, dir_id: NodeId) -> FileCount {
+        let mut descendants = FileCount::new();
+
+        let children = dir_id.children(&self.arena).collect::<Vec<_>>();
+
+        for child_id in children {
+            descendants.update(self.arena[child_id].get());
+
+            if self.arena[child_id].get().is_dir() {
+                descendants.update_from_count(self.recount_descendants(child_id));
+            }
+        }
+
+        self.arena[dir_id].get_mut().set_descendants(descendants);
+
+        descendants
+    }
+
+    /// Finds the deepest directory of the [Tree] that `path` is or resides in. Returns `None` if
+    /// `path` lies outside of the root.
+    fn nearest_dir(&self, path: &Path) -> Option<NodeId> {
+        let root_path = self.arena[self.root_id].get().path();
+        let relative = path.strip_prefix(root_path).ok()?;
+
+        let mut dir_id = self.root_id;
+
+        for component in relative.components() {
+            match self.child_named(dir_id, component.as_os_str()) {
+                Some(child_id) if self.arena[child_id].get().is_dir() => dir_id = child_id,
+                _ => break,
+            }
+        }
+
+        Some(dir_id)
+    }
+
+    /// The child of `dir_id` whose file name is `name`.
+    fn child_named(&self, dir_id: NodeId, name: &OsStr) -> Option<NodeId> {
+        dir_id
+            .children(&self.arena)
+            .find(|child_id| self.arena[*child_id].get().file_name() == name)
+    }
+
+    /// Reads the immediate contents of the directory at `dir_id` again and reconciles them with
+    /// its children: vanished entries are removed, existing ones are updated in place while
+    /// keeping their descendants, and new directories are traversed. Files with multiple hard
+    /// links that were added, updated, or removed along the way are recorded in `relinked`.
+    /// Returns the new size of the directory in bytes along with its size of the other
+    /// [DiskUsage].
+    ///
+    /// [DiskUsage]: crate::render::disk_usage::file_size::DiskUsage
+    fn reread_dir(&mut self, dir_id: NodeId, relinked: &mut HashSet<FileId>) -> Result<(u64, u64)> {
+        let (dir_path, depth) = {
+            let dir = self.arena[dir_id].get();
+            (dir.path().to_path_buf(), dir.depth())
+        };
+
+        let mut stale = dir_id
+            .children(&self.arena)
+            .map(|child_id| (self.arena[child_id].get().file_name().to_owned(), child_id))
+            .collect::<HashMap<OsString, NodeId>>();
+
+        let mut walker = walk_builder(&self.ctx, &dir_path, false)?;
+        walker.max_depth(Some(1));
+
+        let mut bytes = 0;
+        let mut other_bytes = 0;
+
+        for dir_entry in walker.build().filter_map(StdResult::ok) {
+            if dir_entry.depth() == 0 {
+                // Only sized if the directory's own blocks are accounted for.
+                if let Ok(dir) = Node::try_from((dir_entry, &self.ctx)) {
+                    let (own_bytes, own_other_bytes) = sizes_of(&dir);
+                    bytes += own_bytes;
+                    other_bytes += own_other_bytes;
+                }
+
+                continue;
+            }
+
+            let Ok(mut node) = Node::try_from((dir_entry, &self.ctx)) else {
+                continue;
+            };
+
+            node.set_depth(depth + 1);
+
+            let existing = stale.remove(node.file_name()).filter(|child_id| {
+                let is_same_kind = self.arena[*child_id].get().is_dir() == node.is_dir();
+
+                if !is_same_kind {
+                    self.remove_subtree(*child_id, relinked);
+                }
+
+                is_same_kind
+            });
+
+            let child_id = match existing {
+                Some(child_id) => {
+                    let child = self.arena[child_id].get_mut();
+
+                    relinked.extend(file_id(child));
+
+                    // Directories keep their aggregate size; their contents are untouched.
+                    if node.is_dir() {
+                        if let Some(file_size) = child.file_size().cloned() {
+                            node.set_file_size(file_size);
+                        }
+
+                        if let Some(file_size) = child.other_file_size().cloned() {
+                            node.set_other_file_size(file_size);
+                        }
+
+                        if child.is_incomplete() {
+                            node.mark_incomplete();
+                        }
+                    }
+
+                    relinked.extend(file_id(&node));
+
+                    *child = node;
+                    child_id
+                }
+                None if node.is_dir() => {
+                    let subtree_id = self.traverse_dir(node.path(), depth + 1)?;
+
+                    for node_id in subtree_id.descendants(&self.arena) {
+                        relinked.extend(file_id(self.arena[node_id].get()));
+                    }
+
+                    subtree_id
+                }
+                None => {
+                    relinked.extend(file_id(&node));
+                    self.arena.new_node(node)
+                }
+            };
+
+            let (child_bytes, child_other_bytes) =
+                charges_of(self.arena[child_id].get(), self.ctx.hardlinks);
+
+            bytes += child_bytes;
+            other_bytes += child_other_bytes;
+
+            if self.arena[child_id].parent().is_none() {
+                dir_id.append(child_id, &mut self.arena);
+            }
+        }
+
+        for (_, child_id) in stale {
+            self.remove_subtree(child_id, relinked);
+        }
+
+        self.set_dir_size(bytes, other_bytes, dir_id);
+
+        Ok((bytes, other_bytes))
+    }
+
+    /// Removes the subtree at `node_id`, recording the files with multiple hard links within it
+    /// in `relinked`.
+    fn remove_subtree(&mut self, node_id: NodeId, relinked: &mut HashSet<FileId>) {
+        for descendant_id in node_id.descendants(&self.arena) {
+            relinked.extend(file_id(self.arena[descendant_id].get()));
+        }
+
+        node_id.remove_subtree(&mut self.arena);
+    }
+
+    /// Traverses the directory at `path` that is new to the [Tree] and returns the detached
+    /// subtree which is assembled just like the [Tree] at large.
+    fn traverse_dir(&mut self, path: &Path, depth: usize) -> Result<NodeId> {
+        let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
+        let mut root_id = None;
+
+        for dir_entry in walk_builder(&self.ctx, path, false)?
+            .build()
+            .filter_map(StdResult::ok)
+        {
+            if let Ok(node) = Node::try_from((dir_entry, &self.ctx)) {
+                Self::insert_node(&mut self.arena, &mut branches, &mut root_id, node)?;
+            }
+        }
+
+        let root_id = root_id.ok_or(Error::MissingRoot)?;
+
+        let mut column_properties = ColumnProperties::from(&self.ctx);
+
+        Self::assemble(
+            &mut self.arena,
+            root_id,
+            &mut branches,
+            &mut column_properties,
+            &self.ctx,
+        );
+
+        let descendants = root_id.descendants(&self.arena).collect::<Vec<_>>();
+
+        for node_id in descendants {
+            let node = self.arena[node_id].get_mut();
+            node.set_depth(node.depth() + depth);
+        }
+
+        Ok(root_id)
+    }
+
+    /// Ranks each link of the files in `relinked` anew among all of their links in the [Tree]
+    /// and carries any resulting change in what is charged for them up their ancestor chains,
+    /// recording the directories that were resized in `resized`. See [HardLink].
+    fn rerank_hard_links(&mut self, relinked: &HashSet<FileId>, resized: &mut HashSet<NodeId>) {
+        let mut links: HashMap<FileId, Vec<NodeId>> = HashMap::new();
+
+        for node_id in self.root_id.descendants(&self.arena) {
+            let node = self.arena[node_id].get();
+
+            let id = node
+                .inode()
+                .filter(|_| !node.is_dir())
+                .map(|inode| (inode.ino, inode.dev));
+
+            if let Some(id) = id.filter(|id| relinked.contains(id)) {
+                links.entry(id).or_default().push(node_id);
+            }
+        }
+
+        let accounting = self.ctx.hardlinks;
+
+        for mut node_ids in links.into_values() {
+            node_ids.sort_by(|id_a, id_b| {
+                let path_a = self.arena[*id_a].get().path();
+                let path_b = self.arena[*id_b].get().path();
+                path_a.cmp(path_b)
+            });
+
+            let count = node_ids.len() as u64;
+
+            for (rank, node_id) in (0..).zip(node_ids) {
+                let node = self.arena[node_id].get_mut();
+                let old_charges = charges_of(node, accounting);
+
+                node.set_hard_link(HardLink { rank, count });
+                let new_charges = charges_of(node, accounting);
+
+                if new_charges != old_charges {
+                    self.resize_ancestors(node_id, old_charges, new_charges, resized);
+                }
+            }
+        }
+    }
+
+    /// Carries the change of what the [Node] at `node_id` contributes to the size of its
+    /// ancestors from `old_sizes` to `new_sizes` up its ancestor chain, recording the directories
+    /// that were resized in `resized`.
+    fn resize_ancestors(
+        &mut self,
+        node_id: NodeId,
+        old_sizes: (u64, u64),
+        new_sizes: (u64, u64),
+        resized: &mut HashSet<NodeId>,
+    ) {
+        let (old_bytes, old_other_bytes) = old_sizes;
+        let (new_bytes, new_other_bytes) = new_sizes;
+
+        let ancestors = node_id.ancestors(&self.arena).skip(1).collect::<Vec<_>>();
+
+        for ancestor_id in ancestors {
+            let (bytes, other_bytes) = sizes_of(self.arena[ancestor_id].get());
+
+            self.set_dir_size(
+                (bytes + new_bytes).saturating_sub(old_bytes),
+                (other_bytes + new_other_bytes).saturating_sub(old_other_bytes),
+                ancestor_id,
+            );
+
+            resized.insert(ancestor_id);
+        }
+    }
+
+    /// Sets the aggregate size of the directory at `dir_id` to `bytes` and its size of the other
+    /// [DiskUsage] to `other_bytes`.
+    ///
+    /// [DiskUsage]: crate::render::disk_usage::file_size::DiskUsage
+    fn set_dir_size(&mut self, bytes: u64, other_bytes: u64, dir_id: NodeId) {
+        let ctx = &self.ctx;
+        let dir = self.arena[dir_id].get_mut();
+
+        dir.clear_file_size();
+
+        if bytes > 0 {
+            let mut file_size = FileSize::new(bytes, ctx.disk_usage, ctx.human, ctx.unit);
+            file_size.precompute_unpadded_display();
+            dir.set_file_size(file_size);
+        }
+
+        if other_bytes > 0 {
+            let disk_usage = ctx.disk_usage.other();
+            let mut file_size = FileSize::new(other_bytes, disk_usage, ctx.human, ctx.unit);
+            file_size.precompute_unpadded_display();
+            dir.set_other_file_size(file_size);
+        }
+    }
+}
+
+/// The size of `node` in bytes along with its size of the other [DiskUsage].
+///
+/// [DiskUsage]: crate::render::disk_usage::file_size::DiskUsage
+fn sizes_of(node: &Node) -> (u64, u64) {
+    (
+        node.file_size().map_or(0, |fs| fs.bytes),
+        node.other_file_size().map_or(0, |fs| fs.bytes),
+    )
+}
+
+/// What `node` contributes to the size of its parent directory in bytes along with what it
+/// contributes to its size of the other [DiskUsage], see [Node::charged_bytes].
+///
+/// [DiskUsage]: crate::render::disk_usage::file_size::DiskUsage
+fn charges_of(node: &Node, accounting: Accounting) -> (u64, u64) {
+    (
+        node.charged_bytes(accounting),
+        node.charged_other_bytes(accounting),
+    )
+}
+
+/// Identifies `node` if it's a file with multiple hard links, or one that had them when it was
+/// last ranked.
+fn file_id(node: &Node) -> Option<FileId> {
+    node.inode()
+        .filter(|_| node.is_hard_linked() || node.hard_link().is_some())
+        .map(|inode| (inode.ino, inode.dev))
+}
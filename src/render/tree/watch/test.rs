@@ -0,0 +1,180 @@
+use crate::render::{
+    context::Context,
+    tree::{display::Regular, Tree},
+};
+use clap::Parser;
+use indextree::NodeId;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+
+/// Initializes a [Tree] rooted at `root` in `--watch` mode.
+fn watch(root: &Path, args: &[&str]) -> Tree<Regular> {
+    let ctx = Context::try_parse_from(
+        [
+            "erd",
+            "--watch",
+            "--disk-usage",
+            "logical",
+            "--threads",
+            "1",
+        ]
+        .into_iter()
+        .chain(args.iter().copied())
+        .chain([root.to_str().unwrap()]),
+    )
+    .unwrap();
+
+    Tree::<Regular>::try_init(ctx).unwrap()
+}
+
+/// The shown [Node] at `path`, if any.
+///
+/// [Node]: crate::render::tree::node::Node
+fn find(tree: &Tree<Regular>, path: &Path) -> Option<NodeId> {
+    tree.root_id
+        .descendants(&tree.arena)
+        .find(|node_id| tree.arena[*node_id].get().path() == path)
+}
+
+/// The size of the shown [Node] at `path` in bytes.
+///
+/// [Node]: crate::render::tree::node::Node
+fn size_of(tree: &Tree<Regular>, path: &Path) -> u64 {
+    let node_id = find(tree, path).unwrap();
+    tree.arena[node_id]
+        .get()
+        .file_size()
+        .map_or(0, |fs| fs.bytes)
+}
+
+/// A temporary directory along with its canonical path, which is what the [Tree] is rooted at.
+fn tempdir() -> (TempDir, PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let root = fs::canonicalize(dir.path()).unwrap();
+    (dir, root)
+}
+
+#[test]
+fn refresh_updates_ancestor_sizes() {
+    let (_dir, root) = tempdir();
+
+    fs::create_dir_all(root.join("a/b")).unwrap();
+    fs::write(root.join("a/b/x"), [0; 100]).unwrap();
+    fs::write(root.join("y"), [0; 10]).unwrap();
+
+    let mut tree = watch(&root, &[]);
+
+    assert_eq!(size_of(&tree, &root), 110);
+
+    fs::write(root.join("a/b/z"), [0; 1000]).unwrap();
+    fs::create_dir(root.join("c")).unwrap();
+    fs::write(root.join("c/w"), [0; 5]).unwrap();
+    tree.refresh([root.join("a/b/z"), root.join("c")]).unwrap();
+
+    assert_eq!(size_of(&tree, &root.join("a/b")), 1100);
+    assert_eq!(size_of(&tree, &root.join("a")), 1100);
+    assert_eq!(size_of(&tree, &root.join("c")), 5);
+    assert_eq!(size_of(&tree, &root), 1115);
+    assert_eq!(tree.arena[tree.root_id].get().descendants().total(), 7);
+
+    fs::remove_dir_all(root.join("a")).unwrap();
+    tree.refresh([root.join("a")]).unwrap();
+
+    assert_eq!(size_of(&tree, &root), 15);
+    assert_eq!(tree.arena[tree.root_id].get().descendants().total(), 3);
+    assert_eq!(tree.nearest_dir(&root.join("a")), Some(tree.root_id));
+}
+
+#[test]
+fn refresh_filters_anew() {
+    let (_dir, root) = tempdir();
+
+    fs::create_dir(root.join("a")).unwrap();
+    fs::write(root.join("a/x"), [0; 100]).unwrap();
+    fs::write(root.join("a/y"), [0; 10]).unwrap();
+    fs::create_dir(root.join("b")).unwrap();
+    fs::write(root.join("b/z"), [0; 20]).unwrap();
+
+    let mut tree = watch(&root, &["--min-size", "50"]);
+
+    assert!(find(&tree, &root.join("a/y")).is_none());
+    assert!(find(&tree, &root.join("b")).is_none());
+    assert_eq!(size_of(&tree, &root), 130);
+
+    // Entries that were filtered out still count once something next to them changes.
+    fs::write(root.join("a/x"), [0; 200]).unwrap();
+    tree.refresh([root.join("a/x")]).unwrap();
+
+    assert!(find(&tree, &root.join("a/y")).is_none());
+    assert_eq!(size_of(&tree, &root.join("a")), 210);
+    assert_eq!(size_of(&tree, &root), 230);
+
+    // Entries that were filtered out are shown once they're in range.
+    fs::write(root.join("b/z"), [0; 60]).unwrap();
+    tree.refresh([root.join("b/z")]).unwrap();
+
+    assert_eq!(size_of(&tree, &root.join("b/z")), 60);
+    assert_eq!(size_of(&tree, &root.join("b")), 60);
+    assert_eq!(size_of(&tree, &root), 270);
+}
+
+#[test]
+fn refresh_collapses_once() {
+    let (_dir, root) = tempdir();
+
+    fs::create_dir(root.join("a")).unwrap();
+    fs::write(root.join("a/x"), [0; 100]).unwrap();
+
+    for name in ["p", "q", "r"] {
+        fs::write(root.join(name), [0; 5]).unwrap();
+    }
+
+    let mut tree = watch(&root, &["--collapse", "10%"]);
+    let shown = |tree: &Tree<Regular>| tree.root_id.children(&tree.arena).count();
+
+    assert_eq!(shown(&tree), 2);
+
+    fs::write(root.join("s"), [0; 5]).unwrap();
+    tree.refresh([root.join("s")]).unwrap();
+
+    assert_eq!(shown(&tree), 2);
+    assert_eq!(size_of(&tree, &root), 120);
+    assert_eq!(tree.arena[tree.root_id].get().descendants().total(), 6);
+}
+
+#[cfg(unix)]
+#[test]
+fn refresh_recharges_hard_links() {
+    let (_dir, root) = tempdir();
+
+    for dir in ["a", "b", "c"] {
+        fs::create_dir(root.join(dir)).unwrap();
+    }
+
+    fs::write(root.join("a/x"), [0; 100]).unwrap();
+    fs::hard_link(root.join("a/x"), root.join("b/x")).unwrap();
+
+    let mut tree = watch(&root, &["--hardlinks", "first"]);
+
+    assert_eq!(size_of(&tree, &root.join("a")), 100);
+    assert_eq!(size_of(&tree, &root.join("b")), 0);
+    assert_eq!(size_of(&tree, &root), 100);
+
+    // The link that comes first is gone, so the one that's left is charged instead.
+    fs::remove_file(root.join("a/x")).unwrap();
+    tree.refresh([root.join("a/x")]).unwrap();
+
+    assert_eq!(size_of(&tree, &root.join("b")), 100);
+    assert_eq!(size_of(&tree, &root), 100);
+
+    // A new link that comes later in name order isn't charged.
+    fs::hard_link(root.join("b/x"), root.join("c/x")).unwrap();
+    tree.refresh([root.join("c/x")]).unwrap();
+
+    assert_eq!(size_of(&tree, &root.join("b")), 100);
+    assert_eq!(size_of(&tree, &root.join("c")), 0);
+    assert_eq!(size_of(&tree, &root), 100);
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::module_name_repetitions)]
 use is_terminal::IsTerminal;
-use std::io::{stdin, stdout};
+use std::io::{stderr, stdout};
 
 #[cfg(windows)]
 mod windows;
@@ -8,17 +8,16 @@ mod windows;
 #[cfg(unix)]
 mod unix;
 
-/// Is stdin connected to a tty? Should be `false` if `erdtree` is on the receiving end of a
-/// pipeline.
-pub fn stdin_is_tty() -> bool {
-    stdin().is_terminal()
-}
-
 /// Is stdout connected to a tty? Should be `false` if output is redirected to a file.
 pub fn stdout_is_tty() -> bool {
     stdout().is_terminal()
 }
 
+/// Is stderr connected to a tty? Should be `false` if diagnostics are redirected to a file.
+pub fn stderr_is_tty() -> bool {
+    stderr().is_terminal()
+}
+
 /// Attempts to get the current size of the tty's window. Returns `None` if stdout isn't tty or if
 /// failed to get width.
 pub fn get_window_width(stdout_is_tty: bool) -> Option<usize> {
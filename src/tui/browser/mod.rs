@@ -0,0 +1,323 @@
+use crate::render::{
+    context::{sort::SortType, Context},
+    styles,
+    tree::{display::Regular, node::Node, Tree},
+};
+use indextree::NodeId;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// Unit tests for navigating the browser.
+#[cfg(test)]
+mod test;
+
+/// Marker preceding the name of a directory whose contents are shown.
+const EXPANDED: &str = "\u{25be} ";
+
+/// Marker preceding the name of a directory whose contents are hidden.
+const COLLAPSED: &str = "\u{25b8} ";
+
+/// State of the interactive browser. The [Tree] is assembled once up-front and all subsequent
+/// operations, including drilling down into a directory, merely change what part of it is shown.
+pub struct Browser {
+    tree: Tree<Regular>,
+    root_id: NodeId,
+    expanded: HashSet<NodeId>,
+    rows: Vec<Row>,
+    cursor: usize,
+    offset: usize,
+}
+
+/// A single visible line of the browser.
+pub struct Row {
+    pub node_id: NodeId,
+    prefix: String,
+}
+
+/// Wrapper to render a [Node] exactly as it is in the regular tree view.
+struct Line<'a> {
+    node: &'a Node,
+    prefix: &'a str,
+    ctx: &'a Context,
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.node.tree_display(f, self.prefix, self.ctx)
+    }
+}
+
+impl Browser {
+    /// Initializes the browser with directories expanded up to `--level`. If `--level` isn't
+    /// provided then only the contents of the root directory are shown.
+    pub fn new(tree: Tree<Regular>) -> Self {
+        let root_id = tree.root_id();
+        let arena = tree.arena();
+
+        let max_depth = match tree.context().level() {
+            usize::MAX => 1,
+            level => level,
+        };
+
+        let expanded = root_id
+            .descendants(arena)
+            .filter(|id| {
+                let node = arena[*id].get();
+                node.is_dir() && node.depth() < max_depth
+            })
+            .collect();
+
+        let mut browser = Self {
+            tree,
+            root_id,
+            expanded,
+            rows: vec![],
+            cursor: 0,
+            offset: 0,
+        };
+
+        browser.refresh();
+        browser
+    }
+
+    /// All rows that are currently visible.
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Index of the selected row.
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Index of the first row shown on screen.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Sets the width available to each line so that they're truncated to fit.
+    pub fn set_width(&mut self, width: usize) {
+        let ctx = self.tree.context_mut();
+        ctx.truncate = true;
+        ctx.window_width = Some(width);
+    }
+
+    /// Renders the given row as it would appear in the tree view.
+    pub fn line(&self, row: &Row) -> String {
+        let line = Line {
+            node: self.tree.arena()[row.node_id].get(),
+            prefix: &row.prefix,
+            ctx: self.tree.context(),
+        };
+
+        line.to_string()
+    }
+
+    /// Summary of what is being shown.
+    pub fn header(&self) -> String {
+        let ctx = self.tree.context();
+        let root = self.tree.arena()[self.root_id].get();
+
+        let sort = match ctx.sort {
+            SortType::Name => "name",
+            SortType::Size => "size",
+            SortType::SizeRev => "size-rev",
+            SortType::Count => "count",
+            SortType::CountRev => "count-rev",
+        };
+
+        format!("{}  (sort: {sort})", root.path().display())
+    }
+
+    /// Moves the selection by `delta` rows.
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// Moves the selection to the first row.
+    pub fn move_to_first(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Moves the selection to the last row.
+    pub fn move_to_last(&mut self) {
+        self.cursor = self.rows.len().saturating_sub(1);
+    }
+
+    /// Adjusts the scroll offset so that the selection is within a viewport of `height` rows.
+    pub fn scroll_into_view(&mut self, height: usize) {
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if height > 0 && self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+    }
+
+    /// Shows the contents of the selected directory or, if they're already shown, moves the
+    /// selection to its first child.
+    pub fn expand(&mut self) {
+        let node_id = self.selected();
+
+        if !self.tree.arena()[node_id].get().is_dir() {
+            return;
+        }
+
+        if self.expanded.insert(node_id) {
+            self.refresh();
+        } else if node_id.children(self.tree.arena()).next().is_some() {
+            self.move_by(1);
+        }
+    }
+
+    /// Hides the contents of the selected directory or, if they're already hidden, moves the
+    /// selection to its parent directory.
+    pub fn collapse(&mut self) {
+        let node_id = self.selected();
+
+        if node_id != self.root_id && self.expanded.remove(&node_id) {
+            self.refresh();
+            return;
+        }
+
+        if node_id == self.root_id {
+            return;
+        }
+
+        if let Some(parent_id) = self.tree.arena()[node_id].parent() {
+            self.select(parent_id);
+        }
+    }
+
+    /// Toggles whether or not the contents of the selected directory are shown.
+    pub fn toggle(&mut self) {
+        if self.expanded.contains(&self.selected()) {
+            self.collapse();
+        } else {
+            self.expand();
+        }
+    }
+
+    /// Makes the selected directory the root of what is shown.
+    pub fn drill_down(&mut self) {
+        let node_id = self.selected();
+
+        if !self.tree.arena()[node_id].get().is_dir() || node_id == self.root_id {
+            return;
+        }
+
+        self.root_id = node_id;
+        self.expanded.insert(node_id);
+        self.cursor = 0;
+        self.offset = 0;
+        self.refresh();
+    }
+
+    /// Makes the parent of the current root the root of what is shown, selecting the previous
+    /// root.
+    pub fn ascend(&mut self) {
+        let previous_root = self.root_id;
+
+        let Some(parent_id) = self.tree.arena()[previous_root].parent() else {
+            return;
+        };
+
+        self.root_id = parent_id;
+        self.expanded.insert(parent_id);
+        self.refresh();
+        self.select(previous_root);
+    }
+
+    /// Cycles through the available sort-orders, keeping the selection.
+    pub fn cycle_sort(&mut self) {
+        let selected = self.selected();
+        let ctx = self.tree.context_mut();
+
+        ctx.sort = match ctx.sort {
+            SortType::Size => SortType::SizeRev,
+            SortType::SizeRev => SortType::Count,
+            SortType::Count => SortType::CountRev,
+            SortType::CountRev => SortType::Name,
+            SortType::Name => SortType::Size,
+        };
+
+        self.tree.resort();
+        self.refresh();
+        self.select(selected);
+    }
+
+    /// Toggles the columns shown by `--long`.
+    #[cfg(unix)]
+    pub fn toggle_long(&mut self) {
+        let ctx = self.tree.context_mut();
+        ctx.long = !ctx.long;
+    }
+
+    /// The currently selected node.
+    fn selected(&self) -> NodeId {
+        self.rows
+            .get(self.cursor)
+            .map_or(self.root_id, |row| row.node_id)
+    }
+
+    /// Selects the row of `node_id` if it's visible.
+    fn select(&mut self, node_id: NodeId) {
+        if let Some(index) = self.rows.iter().position(|row| row.node_id == node_id) {
+            self.cursor = index;
+        }
+    }
+
+    /// Recomputes the visible rows.
+    fn refresh(&mut self) {
+        let mut rows = vec![];
+
+        self.push_rows(self.root_id, "", true, &mut rows);
+
+        self.rows = rows;
+        self.cursor = self.cursor.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Pushes the row for `node_id` followed by those of its descendants if it's expanded.
+    fn push_rows(&self, node_id: NodeId, base_prefix: &str, is_last: bool, rows: &mut Vec<Row>) {
+        let arena = self.tree.arena();
+        let node = arena[node_id].get();
+        let theme = styles::get_tree_theme().unwrap();
+        let is_expanded = self.expanded.contains(&node_id);
+
+        let marker = match (node.is_dir(), is_expanded) {
+            (false, _) => "",
+            (true, true) => EXPANDED,
+            (true, false) => COLLAPSED,
+        };
+
+        let (prefix, child_prefix) = if node_id == self.root_id {
+            (String::new(), String::new())
+        } else if is_last {
+            (
+                format!("{base_prefix}{}{marker}", theme.get("uprt").unwrap()),
+                format!("{base_prefix}{}", styles::SEP),
+            )
+        } else {
+            (
+                format!("{base_prefix}{}{marker}", theme.get("vtrt").unwrap()),
+                format!("{base_prefix}{}", theme.get("vt").unwrap()),
+            )
+        };
+
+        rows.push(Row { node_id, prefix });
+
+        if !is_expanded {
+            return;
+        }
+
+        let mut children = node_id.children(arena).peekable();
+
+        while let Some(child_id) = children.next() {
+            let is_last = children.peek().is_none();
+            self.push_rows(child_id, &child_prefix, is_last, rows);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use super::Browser;
+use crate::render::{
+    context::{sort::SortType, Context},
+    styles,
+    tree::{display::Regular, Tree},
+};
+use clap::Parser;
+use std::sync::Once;
+
+static STYLES: Once = Once::new();
+
+/// Initializes a [Browser] over the test data sorted by name.
+fn browser() -> Browser {
+    STYLES.call_once(|| styles::init(true));
+
+    let ctx = Context::try_parse_from([
+        "erd",
+        "--disk-usage",
+        "logical",
+        "--sort",
+        "name",
+        "--threads",
+        "1",
+        "tests/data",
+    ])
+    .unwrap();
+
+    Browser::new(Tree::<Regular>::try_init(ctx).unwrap())
+}
+
+/// File names of the visible rows.
+fn names(browser: &Browser) -> Vec<String> {
+    browser
+        .rows()
+        .iter()
+        .map(|row| {
+            browser.tree.arena()[row.node_id]
+                .get()
+                .file_name()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
+const ROOT: [&str; 7] = [
+    "data",
+    "dream_cycle",
+    "lipsum",
+    "necronomicon.txt",
+    "nemesis.txt",
+    "nylarlathotep.txt",
+    "the_yellow_king",
+];
+
+#[test]
+fn initial_rows() {
+    let browser = browser();
+
+    assert_eq!(names(&browser), ROOT);
+    assert_eq!(browser.cursor(), 0);
+}
+
+#[test]
+fn expand() {
+    let mut browser = browser();
+
+    browser.move_by(1);
+    browser.expand();
+
+    assert_eq!(
+        names(&browser),
+        [
+            "data",
+            "dream_cycle",
+            "polaris.txt",
+            "lipsum",
+            "necronomicon.txt",
+            "nemesis.txt",
+            "nylarlathotep.txt",
+            "the_yellow_king",
+        ]
+    );
+    assert_eq!(browser.cursor(), 1);
+
+    // Expanding an expanded directory selects its first child.
+    browser.expand();
+    assert_eq!(browser.cursor(), 2);
+
+    // Files can't be expanded.
+    browser.expand();
+    assert_eq!(names(&browser).len(), 8);
+    assert_eq!(browser.cursor(), 2);
+}
+
+#[test]
+fn collapse() {
+    let mut browser = browser();
+
+    browser.move_by(1);
+    browser.expand();
+    browser.move_by(1);
+
+    // Collapsing a file selects its parent.
+    browser.collapse();
+    assert_eq!(names(&browser).len(), 8);
+    assert_eq!(browser.cursor(), 1);
+
+    browser.collapse();
+    assert_eq!(names(&browser), ROOT);
+    assert_eq!(browser.cursor(), 1);
+
+    // Collapsing a collapsed directory selects its parent.
+    browser.collapse();
+    assert_eq!(browser.cursor(), 0);
+
+    // The root is never collapsed.
+    browser.collapse();
+    assert_eq!(names(&browser), ROOT);
+    assert_eq!(browser.cursor(), 0);
+}
+
+#[test]
+fn drill_down_and_ascend() {
+    let mut browser = browser();
+
+    browser.move_to_last();
+    browser.drill_down();
+
+    assert_eq!(names(&browser), ["the_yellow_king", "cassildas_song.md"]);
+    assert_eq!(browser.cursor(), 0);
+
+    // Files can't be drilled into.
+    browser.move_by(1);
+    browser.drill_down();
+    assert_eq!(names(&browser), ["the_yellow_king", "cassildas_song.md"]);
+
+    browser.ascend();
+    assert_eq!(
+        names(&browser),
+        [
+            "data",
+            "dream_cycle",
+            "lipsum",
+            "necronomicon.txt",
+            "nemesis.txt",
+            "nylarlathotep.txt",
+            "the_yellow_king",
+            "cassildas_song.md",
+        ]
+    );
+    assert_eq!(browser.cursor(), 6);
+
+    // The root of the tree has no parent to ascend to.
+    browser.ascend();
+    assert_eq!(names(&browser).len(), 8);
+    assert_eq!(browser.cursor(), 6);
+}
+
+#[test]
+fn cycle_sort() {
+    let mut browser = browser();
+
+    browser.move_by(2);
+    browser.cycle_sort();
+
+    assert_eq!(browser.tree.context().sort, SortType::Size);
+    assert_eq!(
+        names(&browser),
+        [
+            "data",
+            "lipsum",
+            "dream_cycle",
+            "nemesis.txt",
+            "the_yellow_king",
+            "nylarlathotep.txt",
+            "necronomicon.txt",
+        ]
+    );
+
+    // The selection follows the node rather than the row.
+    assert_eq!(browser.cursor(), 1);
+
+    for _ in 0..4 {
+        browser.cycle_sort();
+    }
+
+    assert_eq!(browser.tree.context().sort, SortType::Name);
+    assert_eq!(names(&browser), ROOT);
+    assert_eq!(browser.cursor(), 2);
+}
+
+#[test]
+fn scroll_into_view() {
+    let mut browser = browser();
+
+    browser.move_to_last();
+    browser.scroll_into_view(3);
+    assert_eq!(browser.cursor(), 6);
+    assert_eq!(browser.offset(), 4);
+
+    // Moving within the viewport doesn't scroll.
+    browser.move_by(-2);
+    browser.scroll_into_view(3);
+    assert_eq!(browser.offset(), 4);
+
+    browser.move_to_first();
+    browser.scroll_into_view(3);
+    assert_eq!(browser.offset(), 0);
+}
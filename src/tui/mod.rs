@@ -0,0 +1,171 @@
+use crate::render::{
+    context::Context,
+    tree::{display::Regular, Tree},
+};
+use browser::Browser;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    error::Error,
+    io::{self, Stdout, Write},
+};
+
+/// State and operations of the interactive browser independent of the terminal.
+mod browser;
+
+/// Key bindings shown at the bottom of the screen.
+#[cfg(unix)]
+const HELP: &str = "\u{2191}\u{2193} move  \u{2190}\u{2192} collapse/expand  space toggle  enter drill down  backspace up  s sort  L long  q quit";
+
+/// Key bindings shown at the bottom of the screen.
+#[cfg(not(unix))]
+const HELP: &str = "\u{2191}\u{2193} move  \u{2190}\u{2192} collapse/expand  space toggle  enter drill down  backspace up  s sort  q quit";
+
+/// Number of lines taken up by the header and the footer.
+const CHROME_HEIGHT: u16 = 2;
+
+/// Width of the column that indicates the selection.
+const GUTTER_WIDTH: usize = 2;
+
+/// Restores the terminal to its original state when dropped, including when unwinding.
+struct TerminalGuard(Stdout);
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+        Ok(Self(stdout))
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(self.0, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Assembles the [Tree] and lets the user browse it until they quit.
+pub fn run(mut ctx: Context) -> Result<(), Box<dyn Error>> {
+    if !ctx.stdout_is_tty {
+        return Err("--interactive requires stdout to be a terminal".into());
+    }
+
+    // Long attributes are always gathered so that they can be toggled without traversing again.
+    #[cfg(unix)]
+    let long = std::mem::replace(&mut ctx.long, true);
+
+    #[allow(unused_mut)]
+    let mut tree = Tree::<Regular>::try_init(ctx)?;
+
+    #[cfg(unix)]
+    {
+        tree.context_mut().long = long;
+    }
+
+    let mut browser = Browser::new(tree);
+    let mut guard = TerminalGuard::new()?;
+
+    loop {
+        let (width, height) = terminal::size()?;
+        let viewport = usize::from(height.saturating_sub(CHROME_HEIGHT));
+
+        browser.set_width(usize::from(width).saturating_sub(GUTTER_WIDTH));
+        browser.scroll_into_view(viewport);
+
+        draw(&mut guard.0, &browser, width, height)?;
+
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        if kind == KeyEventKind::Release {
+            continue;
+        }
+
+        let page = isize::try_from(viewport.max(1)).unwrap_or(isize::MAX);
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Up | KeyCode::Char('k') => browser.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => browser.move_by(1),
+            KeyCode::PageUp => browser.move_by(-page),
+            KeyCode::PageDown => browser.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => browser.move_to_first(),
+            KeyCode::End | KeyCode::Char('G') => browser.move_to_last(),
+            KeyCode::Right | KeyCode::Char('l') => browser.expand(),
+            KeyCode::Left | KeyCode::Char('h') => browser.collapse(),
+            KeyCode::Char(' ') => browser.toggle(),
+            KeyCode::Enter => browser.drill_down(),
+            KeyCode::Backspace | KeyCode::Char('u') => browser.ascend(),
+            KeyCode::Char('s') => browser.cycle_sort(),
+            #[cfg(unix)]
+            KeyCode::Char('L') => browser.toggle_long(),
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Draws a single frame.
+fn draw(out: &mut Stdout, browser: &Browser, width: u16, height: u16) -> io::Result<()> {
+    let viewport = usize::from(height.saturating_sub(CHROME_HEIGHT));
+    let width = usize::from(width);
+
+    queue!(
+        out,
+        MoveTo(0, 0),
+        Clear(ClearType::All),
+        SetAttribute(Attribute::Reverse),
+        Print(format!("{:<width$}", fit(&browser.header(), width))),
+        SetAttribute(Attribute::Reset),
+    )?;
+
+    let rows = browser.rows().iter().enumerate();
+
+    for (line, (index, row)) in rows.skip(browser.offset()).take(viewport).enumerate() {
+        let gutter = if index == browser.cursor() {
+            "> "
+        } else {
+            "  "
+        };
+        let y = u16::try_from(line + 1).unwrap_or(u16::MAX);
+
+        queue!(
+            out,
+            MoveTo(0, y),
+            Print(gutter),
+            Print(browser.line(row)),
+            SetAttribute(Attribute::Reset),
+        )?;
+    }
+
+    queue!(
+        out,
+        MoveTo(0, height.saturating_sub(1)),
+        SetAttribute(Attribute::Reverse),
+        Print(format!("{:<width$}", fit(HELP, width))),
+        SetAttribute(Attribute::Reset),
+    )?;
+
+    out.flush()
+}
+
+/// Truncates plain text to fit within `width` columns.
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
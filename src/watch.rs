@@ -0,0 +1,89 @@
+use crate::render::{
+    context::Context,
+    tree::{display::TreeVariant, Tree},
+};
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    terminal::{Clear, ClearType},
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::Display,
+    io::{self, Write},
+    path::PathBuf,
+    sync::mpsc,
+    time::Duration,
+};
+
+/// How long to keep collecting filesystem events after the first one before redrawing, such that
+/// a burst of writes results in a single redraw.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Assembles the [Tree] and redraws it every time files under the root directory change until
+/// interrupted.
+pub fn run<T>(ctx: Context) -> Result<(), Box<dyn Error>>
+where
+    T: TreeVariant,
+    Tree<T>: Display,
+{
+    let mut tree = Tree::<T>::try_init(ctx)?;
+
+    let root = tree.arena()[tree.root_id()].get().path().to_path_buf();
+
+    let (tx, rx) = mpsc::channel();
+
+    let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+
+    draw(&tree)?;
+
+    while let Ok(event) = rx.recv() {
+        let mut paths = HashSet::new();
+        collect_paths(event, &mut paths);
+
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            collect_paths(event, &mut paths);
+        }
+
+        tree.refresh(&paths)?;
+
+        draw(&tree)?;
+    }
+
+    Ok(())
+}
+
+/// Adds the paths affected by `event` to `paths`. Errors reported by the watcher are ignored as
+/// the next successful event brings the [Tree] up to date anyway.
+fn collect_paths(event: notify::Result<notify::Event>, paths: &mut HashSet<PathBuf>) {
+    if let Ok(event) = event {
+        paths.extend(event.paths);
+    }
+}
+
+/// Prints the [Tree] in place of the previous one if stdout is a terminal, otherwise after it
+/// separated by an empty line.
+fn draw<T>(tree: &Tree<T>) -> io::Result<()>
+where
+    T: TreeVariant,
+    Tree<T>: Display,
+{
+    let mut stdout = io::stdout().lock();
+
+    let is_tty = tree.context().stdout_is_tty;
+
+    if is_tty {
+        queue!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    }
+
+    writeln!(stdout, "{tree}")?;
+
+    if !is_tty {
+        writeln!(stdout)?;
+    }
+
+    stdout.flush()
+}
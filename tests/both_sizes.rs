@@ -0,0 +1,41 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn both_sizes() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--import-ncdu",
+            "tests/ncdu/export.json",
+            "--both-sizes",
+            "--ratio"
+        ]),
+        indoc!(
+            "-      -        - ┌─ sign
+            300  B 4096 B   13.65x │  ┌─ cassilda.md
+            300  B 4096 B   13.65x │  ├─ camilla.md
+            300  B 4096 B   13.65x ├─ lake_hali
+            1200 B 4096 B    3.41x ├─ hastur.txt
+            1500 B 8192 B    5.46x carcosa
+
+            1 directory, 4 files"
+        )
+    )
+}
+
+#[test]
+fn both_sizes_physical_first_in_use() {
+    let out = utils::run_cmd(&[
+        "--import-ncdu",
+        "tests/ncdu/export.json",
+        "--both-sizes",
+        "--disk-usage",
+        "physical",
+        "--sort",
+        "size",
+    ]);
+
+    // Sorted by physical size yet logical size still comes first.
+    assert!(out.ends_with("1500 B 8192 B carcosa\n\n1 directory, 4 files"));
+}
@@ -0,0 +1,33 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn breakdown_ext() {
+    assert_eq!(
+        utils::run_cmd(&["--breakdown", "ext", "tests/data"]),
+        indoc!(
+            "1098 B  88.5% 5 files txt
+            143  B  11.5% 1 file  md"
+        )
+    )
+}
+
+#[test]
+fn breakdown_ext_with_pattern() {
+    assert_eq!(
+        utils::run_cmd(&["--breakdown", "ext", "--pattern", "^n", "tests/data"]),
+        indoc!("344 B 100.0% 3 files txt")
+    )
+}
+
+#[test]
+fn breakdown_icon() {
+    let out = utils::run_cmd(&["--breakdown", "icon", "tests/data"]);
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2, "{out}");
+    assert!(lines[0].starts_with("1098 B  88.5% 5 files"), "{out}");
+    assert!(lines[0].ends_with(" txt"), "{out}");
+    assert!(lines[1].ends_with(" md"), "{out}");
+}
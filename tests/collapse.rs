@@ -0,0 +1,55 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn collapse_percent() {
+    assert_eq!(
+        utils::run_cmd(&["--collapse", "15%", "tests/data"]),
+        indoc!(
+            "446  B    ┌─ lipsum.txt
+            446  B ┌─ lipsum
+            308  B │  ┌─ polaris.txt
+            308  B ├─ dream_cycle
+            487  B ├─ <4 smaller entries, 487 B>
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn collapse_size() {
+    assert_eq!(
+        utils::run_cmd(&["--collapse", "200", "--flat", "tests/data"]),
+        indoc!(
+            "1241 B   data
+            487  B   <4 smaller entries, 487 B>
+            308  B   dream_cycle
+            308  B   dream_cycle/polaris.txt
+            446  B   lipsum
+            446  B   lipsum/lipsum.txt
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+/// Only `necronomicon.txt` is smaller than 7% which is left as is.
+#[test]
+fn collapse_single_entry() {
+    assert_eq!(
+        utils::run_cmd(&["--collapse", "7%", "--level", "1", "tests/data"]),
+        utils::run_cmd(&["--level", "1", "tests/data"]),
+    )
+}
+
+/// Entries beneath the directories that were merged still count.
+#[test]
+fn collapse_counts_descendants() {
+    let out = utils::run_cmd(&["--collapse", "30%", "tests/data"]);
+
+    assert!(out.contains("<5 smaller entries, 795 B>"), "{out}");
+    assert!(out.ends_with("3 directories, 6 files"), "{out}");
+}
@@ -0,0 +1,92 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn counts() {
+    assert_eq!(
+        utils::run_cmd(&["--counts", "tests/data"]),
+        indoc!(
+            "143  B                           ┌─ cassildas_song.md
+            143  B 1 file                 ┌─ the_yellow_king
+            100  B                        ├─ nylarlathotep.txt
+            161  B                        ├─ nemesis.txt
+            83   B                        ├─ necronomicon.txt
+            446  B                        │  ┌─ lipsum.txt
+            446  B 1 file                 ├─ lipsum
+            308  B                        │  ┌─ polaris.txt
+            308  B 1 file                 ├─ dream_cycle
+            1241 B 6 files 3 dirs         data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+/// Lays out a directory with many small files next to one with a single larger file such that
+/// sorting by size and by count disagree.
+fn sprawl() -> Result<TempDir, Box<dyn Error>> {
+    let dir = tempfile::tempdir()?;
+    let root = dir.path();
+
+    fs::create_dir_all(root.join("many/nested"))?;
+    fs::create_dir(root.join("few"))?;
+
+    for name in ["a", "b"] {
+        fs::write(root.join("many").join(name), [0; 10])?;
+    }
+
+    fs::write(root.join("many/nested/c"), [0; 10])?;
+    fs::write(root.join("few/d"), [0; 100])?;
+
+    Ok(dir)
+}
+
+#[test]
+fn counts_sort() -> Result<(), Box<dyn Error>> {
+    let dir = sprawl()?;
+    let root = dir.path().to_str().unwrap();
+
+    let out = utils::run_cmd(&["--counts", "--sort", "count", "--level", "1", root]);
+
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].ends_with("1 file                 ┌─ few"), "{out}");
+    assert!(
+        lines[1].ends_with("3 files 1 dir          ├─ many"),
+        "{out}"
+    );
+    assert!(lines[2].contains("4 files 3 dirs         "), "{out}");
+
+    let out = utils::run_cmd(&["--counts", "--sort", "count-rev", "--level", "1", root]);
+
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].ends_with("┌─ many"), "{out}");
+    assert!(lines[1].ends_with("├─ few"), "{out}");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn counts_omit_zeros() -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir()?;
+    let root = dir.path();
+
+    fs::create_dir(root.join("a"))?;
+    fs::write(root.join("a/x"), [0; 3])?;
+    symlink("x", root.join("a/y"))?;
+    symlink("x", root.join("a/z"))?;
+
+    let out = utils::run_cmd(&["--counts", root.to_str().unwrap()]);
+
+    assert!(out.contains("1 file         2 links ┌─ a\n"), "{out}");
+    assert!(out.contains("1 file  1 dir  2 links "), "{out}");
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn csv() {
+    assert_eq!(
+        utils::run_cmd(&["--format", "csv", "--level", "1", "tests/data"]).replace("\r\n", "\n"),
+        indoc!(
+            "path,depth,type,bytes,human_size
+            .,0,directory,1241,1.21 KiB
+            dream_cycle,1,directory,308,308 B
+            lipsum,1,directory,446,446 B
+            necronomicon.txt,1,file,83,83 B
+            nemesis.txt,1,file,161,161 B
+            nylarlathotep.txt,1,file,100,100 B
+            the_yellow_king,1,directory,143,143 B"
+        )
+    )
+}
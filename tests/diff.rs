@@ -0,0 +1,88 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn diff() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("carcosa");
+    let snapshot = tmp.path().join("carcosa.snap");
+
+    fs::create_dir_all(root.join("lake_hali"))?;
+    fs::write(root.join("hastur.txt"), "a".repeat(100))?;
+    fs::write(root.join("yellow_sign.txt"), "a".repeat(50))?;
+    fs::write(root.join("lake_hali").join("cassilda.txt"), "a".repeat(10))?;
+
+    let root = root.to_str().unwrap();
+    let snapshot = snapshot.to_str().unwrap();
+
+    utils::run_cmd(&["--save", snapshot, root]);
+
+    fs::write(
+        tmp.path().join("carcosa").join("hastur.txt"),
+        "a".repeat(300),
+    )?;
+    fs::remove_file(tmp.path().join("carcosa").join("yellow_sign.txt"))?;
+    fs::write(
+        tmp.path().join("carcosa").join("camilla.txt"),
+        "a".repeat(20),
+    )?;
+
+    assert_eq!(
+        utils::run_cmd(&["--diff", snapshot, root]),
+        indoc!(
+            "+20 B  20 B ┌─ camilla.txt [+]
+             -50 B     - ├─ yellow_sign.txt [-]
+            +200 B 300 B ├─ hastur.txt
+            +170 B 330 B carcosa
+
+            1 added, 1 removed, 1 changed"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--diff", snapshot, "--diff-sort", "rel", "--inverted", root]),
+        indoc!(
+            "+170 B 330 B carcosa
+             +20 B  20 B ├─ camilla.txt [+]
+            +200 B 300 B ├─ hastur.txt
+             -50 B     - └─ yellow_sign.txt [-]
+
+            1 added, 1 removed, 1 changed"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diff_everything_removed() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("carcosa");
+    let snapshot = tmp.path().join("carcosa.snap");
+
+    fs::create_dir_all(root.join("lake_hali"))?;
+    fs::write(root.join("hastur.txt"), "a".repeat(100))?;
+    fs::write(root.join("lake_hali").join("cassilda.txt"), "a".repeat(10))?;
+
+    utils::run_cmd(&["--save", snapshot.to_str().unwrap(), root.to_str().unwrap()]);
+
+    fs::remove_file(root.join("hastur.txt"))?;
+    fs::remove_dir_all(root.join("lake_hali"))?;
+
+    assert_eq!(
+        utils::run_cmd(&["--diff", snapshot.to_str().unwrap(), root.to_str().unwrap()]),
+        indoc!(
+            "-10 B   -    ┌─ cassilda.txt [-]
+              -10 B   - ┌─ lake_hali [-]
+             -100 B   - ├─ hastur.txt [-]
+             -110 B 0 B carcosa
+
+             0 added, 3 removed, 0 changed"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+mod utils;
+
+#[test]
+fn dot() {
+    let out = utils::run_cmd(&["--format", "dot", "tests/data"]);
+
+    assert!(out.starts_with("digraph erdtree {"));
+    assert!(out.ends_with('}'));
+    assert!(out.contains(r#"[label="data\n1.21 KiB", shape=folder"#));
+    assert!(out.contains(r#"[label="polaris.txt\n308 B", shape=note"#));
+    assert_eq!(out.matches(" -> ").count(), 9);
+}
+
+#[test]
+fn dot_with_level_and_dirs_only() {
+    let out = utils::run_cmd(&[
+        "--format",
+        "dot",
+        "--level",
+        "1",
+        "--dirs-only",
+        "tests/data",
+    ]);
+
+    assert!(out.contains(r#"[label="dream_cycle\n308 B", shape=folder"#));
+    assert!(!out.contains("polaris.txt"));
+    assert!(!out.contains("nemesis.txt"));
+    assert_eq!(out.matches(" -> ").count(), 3);
+}
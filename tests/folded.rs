@@ -0,0 +1,33 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn folded() {
+    assert_eq!(
+        utils::run_cmd(&["--format", "folded", "tests/data"]),
+        indoc!(
+            "data;dream_cycle;polaris.txt 308
+            data;lipsum;lipsum.txt 446
+            data;necronomicon.txt 83
+            data;nemesis.txt 161
+            data;nylarlathotep.txt 100
+            data;the_yellow_king;cassildas_song.md 143"
+        )
+    )
+}
+
+#[test]
+fn folded_with_level() {
+    assert_eq!(
+        utils::run_cmd(&["--format", "folded", "--level", "1", "tests/data"]),
+        indoc!(
+            "data;dream_cycle 308
+            data;lipsum 446
+            data;necronomicon.txt 83
+            data;nemesis.txt 161
+            data;nylarlathotep.txt 100
+            data;the_yellow_king 143"
+        )
+    )
+}
@@ -36,3 +36,106 @@ fn hardlink() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+/// Lays out two backups that share `a` and `b` through hard links along with a file of their own
+/// each, as produced by `cp -al` or rsnapshot.
+fn backups() -> Result<tempfile::TempDir, Box<dyn Error>> {
+    let dir = tempfile::tempdir()?;
+    let latest = dir.path().join("daily.0");
+    let previous = dir.path().join("daily.1");
+
+    fs::create_dir_all(&latest)?;
+    fs::create_dir_all(&previous)?;
+
+    fs::write(previous.join("a"), [0; 1000])?;
+    fs::write(previous.join("b"), [0; 3000])?;
+    fs::write(previous.join("c"), [0; 100])?;
+    fs::write(latest.join("d"), [0; 500])?;
+
+    fs::hard_link(previous.join("a"), latest.join("a"))?;
+    fs::hard_link(previous.join("b"), latest.join("b"))?;
+
+    Ok(dir)
+}
+
+#[test]
+fn hardlinks_first() -> Result<(), Box<dyn Error>> {
+    let dir = backups()?;
+    let out = utils::run_cmd(&["--flat", dir.path().to_str().unwrap()]);
+
+    assert!(out.contains("4500 B   daily.0\n"));
+    assert!(out.contains("100  B   daily.1\n"));
+
+    Ok(())
+}
+
+#[test]
+fn hardlinks_every() -> Result<(), Box<dyn Error>> {
+    let dir = backups()?;
+    let out = utils::run_cmd(&[
+        "--flat",
+        "--hardlinks",
+        "every",
+        dir.path().to_str().unwrap(),
+    ]);
+
+    assert!(out.contains("4500 B   daily.0\n"));
+    assert!(out.contains("4100 B   daily.1\n"));
+    assert!(out.starts_with("8600 B"));
+
+    Ok(())
+}
+
+#[test]
+fn hardlinks_split() -> Result<(), Box<dyn Error>> {
+    let dir = backups()?;
+    let out = utils::run_cmd(&[
+        "--flat",
+        "--hardlinks",
+        "split",
+        dir.path().to_str().unwrap(),
+    ]);
+
+    assert!(out.contains("2500 B   daily.0\n"));
+    assert!(out.contains("2100 B   daily.1\n"));
+    assert!(out.starts_with("4600 B"));
+
+    Ok(())
+}
+
+/// Links outside of the tree aren't charged anything, so the size of a file is split among its
+/// links within the tree alone.
+#[test]
+fn hardlinks_split_within_tree() -> Result<(), Box<dyn Error>> {
+    let dir = backups()?;
+    let elsewhere = tempfile::tempdir()?;
+    let src = dir.path().join("daily.1").join("b");
+
+    fs::hard_link(src, elsewhere.path().join("b"))?;
+
+    let out = utils::run_cmd(&[
+        "--flat",
+        "--hardlinks",
+        "split",
+        dir.path().to_str().unwrap(),
+    ]);
+
+    assert!(out.contains("2500 B   daily.0\n"));
+    assert!(out.contains("2100 B   daily.1\n"));
+    assert!(out.starts_with("4600 B"));
+
+    Ok(())
+}
+
+#[test]
+fn hardlinks_shared() -> Result<(), Box<dyn Error>> {
+    let dir = backups()?;
+    let out = utils::run_cmd(&["--flat", "--shared", dir.path().to_str().unwrap()]);
+
+    assert!(out.contains("4500 B 4000 B   daily.0\n"));
+    assert!(out.contains("100  B 4000 B   daily.1\n"));
+    assert!(out.contains("1000 B 1000 B   daily.0/a\n"));
+    assert!(out.contains("100  B      -   daily.1/c\n"));
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+mod utils;
+
+#[test]
+fn html() {
+    let out = utils::run_cmd(&["--format", "html", "tests/data"]);
+
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.ends_with("</html>"));
+    assert!(out.contains("<style>") && out.contains("<script>"));
+
+    assert!(out.contains(
+        r#"<li class="directory" data-name="lipsum" data-size="446" data-count="1"><details><summary><span class="entry"><span class="size">446 B</span><span class="pct">35.9%</span><span class="name">lipsum</span><span class="count">1 file</span></span></summary>"#
+    ));
+
+    assert!(out.contains(
+        r#"<li class="file" data-name="necronomicon.txt" data-size="83" data-count="0"><span class="entry"><span class="size">83 B</span><span class="pct">6.7%</span><span class="name">necronomicon.txt</span></span></li>"#
+    ));
+}
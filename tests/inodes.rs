@@ -0,0 +1,39 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn inodes() {
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "inodes", "tests/data"]),
+        indoc!(
+            "1    ┌─ cassildas_song.md
+             2 ┌─ the_yellow_king
+             1 ├─ nylarlathotep.txt
+             1 ├─ nemesis.txt
+             1 ├─ necronomicon.txt
+             1 │  ┌─ lipsum.txt
+             2 ├─ lipsum
+             1 │  ┌─ polaris.txt
+             2 ├─ dream_cycle
+            10 data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn inodes_dirs_only() {
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "inodes", "--dirs-only", "tests/data"]),
+        indoc!(
+            "2 ┌─ the_yellow_king
+             2 ├─ lipsum
+             2 ├─ dream_cycle
+            10 data
+
+            3 directories"
+        )
+    )
+}
@@ -0,0 +1,13 @@
+mod utils;
+
+#[test]
+#[should_panic(expected = "--interactive requires stdout to be a terminal")]
+fn interactive_requires_tty() {
+    utils::run_cmd(&["--interactive", "tests/data"]);
+}
+
+#[test]
+#[should_panic]
+fn interactive_conflicts_with_format() {
+    utils::run_cmd(&["--interactive", "--format", "json", "tests/data"]);
+}
@@ -0,0 +1,102 @@
+#[cfg(unix)]
+mod test {
+    use std::{
+        error::Error,
+        fs,
+        process::{Command, Stdio},
+        thread,
+        time::Duration,
+    };
+    use tempfile::TempDir;
+
+    /// Number of directories and of files in each directory that make traversal take long
+    /// enough to interrupt.
+    const FANOUT: usize = 100;
+
+    /// Lays out [FANOUT] directories with [FANOUT] files each.
+    fn fanout() -> Result<TempDir, Box<dyn Error>> {
+        let dir = TempDir::new()?;
+
+        for i in 0..FANOUT {
+            let subdir = dir.path().join(format!("dir{i}"));
+            fs::create_dir(&subdir)?;
+
+            for j in 0..FANOUT {
+                fs::write(subdir.join(format!("file{j}")), "")?;
+            }
+        }
+
+        Ok(dir)
+    }
+
+    /// Runs `erd` with `args` on `dir`, sending it SIGINT after `delay`. Returns the exit code
+    /// along with what was written to stdout.
+    fn interrupt_after(
+        dir: &TempDir,
+        args: &[&str],
+        delay: Duration,
+    ) -> Result<(Option<i32>, String), Box<dyn Error>> {
+        let child = Command::new(env!("CARGO_BIN_EXE_erd"))
+            .args(["--threads", "1", "--no-config", "--level", "1"])
+            .args(args)
+            .arg(dir.path())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        thread::sleep(delay);
+
+        Command::new("kill")
+            .args(["-INT", &child.id().to_string()])
+            .status()?;
+
+        let output = child.wait_with_output()?;
+
+        Ok((output.status.code(), String::from_utf8(output.stdout)?))
+    }
+
+    /// Runs `erd` with `args` on `dir` until SIGINT arrives in the midst of traversal and
+    /// returns what was written to stdout.
+    fn interrupted(dir: &TempDir, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        // SIGINT has to arrive after the handler is installed but before traversal is done,
+        // which depends on the speed of the machine; the delay is adjusted until it does.
+        let mut delay = Duration::from_millis(50);
+
+        for _ in 0..10 {
+            match interrupt_after(dir, args, delay)? {
+                (Some(130), out) => return Ok(out),
+                (Some(0), _) => delay /= 2,
+                _ => delay *= 2,
+            }
+        }
+
+        panic!("traversal was never interrupted");
+    }
+
+    #[test]
+    fn interrupted_output() -> Result<(), Box<dyn Error>> {
+        let dir = fanout()?;
+        let out = interrupted(&dir, &[])?;
+
+        let root = dir.path().file_name().unwrap().to_string_lossy();
+        let root_line = format!("{root} [incomplete]");
+
+        assert!(out.lines().any(|line| line.ends_with(&root_line)), "{out}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn interrupted_ndjson() -> Result<(), Box<dyn Error>> {
+        let dir = fanout()?;
+        let out = interrupted(&dir, &["--format", "ndjson"])?;
+
+        let root = out.lines().last().unwrap_or_default();
+
+        assert!(root.starts_with(r#"{"path":".","#), "{out}");
+        assert!(root.ends_with(r#","incomplete":true}"#), "{out}");
+
+        Ok(())
+    }
+}
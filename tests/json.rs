@@ -0,0 +1,50 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn json() {
+    assert_eq!(
+        utils::run_cmd(&["--format", "json", "tests/data/lipsum"]),
+        indoc!(
+            r#"{
+              "name": "lipsum",
+              "path": ".",
+              "file_type": "directory",
+              "size": 446,
+              "count": {
+                "dirs": 0,
+                "files": 1,
+                "links": 0
+              },
+              "children": [
+                {
+                  "name": "lipsum.txt",
+                  "path": "lipsum.txt",
+                  "file_type": "file",
+                  "size": 446,
+                  "count": {
+                    "dirs": 0,
+                    "files": 0,
+                    "links": 0
+                  }
+                }
+              ]
+            }"#
+        )
+    )
+}
+
+#[test]
+fn json_with_level() {
+    let out = utils::run_cmd(&["--format", "json", "--level", "1", "tests/data"]);
+
+    assert!(out.contains(r#""path": "dream_cycle""#));
+    assert!(!out.contains("polaris.txt"));
+}
+
+#[test]
+#[should_panic]
+fn json_conflicts_with_flat() {
+    utils::run_cmd(&["--format", "json", "--flat", "tests/data"]);
+}
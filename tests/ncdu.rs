@@ -0,0 +1,62 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::NamedTempFile;
+
+mod utils;
+
+#[test]
+fn ncdu_import() {
+    assert_eq!(
+        utils::run_cmd(&["--import-ncdu", "tests/ncdu/export.json"]),
+        indoc!(
+            "- ┌─ sign
+            300  B │  ┌─ cassilda.md
+            300  B │  ├─ camilla.md
+            300  B ├─ lake_hali
+            1200 B ├─ hastur.txt
+            1500 B carcosa
+
+            1 directory, 4 files"
+        )
+    )
+}
+
+#[test]
+fn ncdu_import_hidden() {
+    let out = utils::run_cmd(&["--import-ncdu", "tests/ncdu/export.json", "--hidden"]);
+
+    assert!(out.contains(".yithian"));
+    assert!(out.contains("1550 B carcosa"));
+}
+
+#[test]
+fn ncdu_import_pattern() {
+    let out = utils::run_cmd(&[
+        "--import-ncdu",
+        "tests/ncdu/export.json",
+        "--pattern",
+        "*.md",
+        "--glob",
+    ]);
+
+    assert!(out.contains("cassilda.md"));
+    assert!(!out.contains("hastur.txt"));
+}
+
+#[test]
+fn ncdu_round_trip() -> Result<(), Box<dyn Error>> {
+    let export = utils::run_cmd(&["--format", "ncdu", "tests/data"]);
+
+    assert!(export.starts_with(r#"[1,2,{"progname":"erdtree""#));
+
+    let file = NamedTempFile::new()?;
+    let path = file.path().to_str().unwrap();
+
+    fs::write(path, export)?;
+
+    let out = utils::run_cmd(&["--import-ncdu", path]);
+
+    assert_eq!(out, utils::run_cmd(&["tests/data"]));
+
+    Ok(())
+}
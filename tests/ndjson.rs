@@ -0,0 +1,106 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::NamedTempFile;
+
+mod utils;
+
+/// Records of `tests/data` in pre-order.
+const RECORDS: &str = indoc!(
+    r#"{"path":".","depth":0,"file_type":"directory","size":1241}
+    {"path":"dream_cycle","depth":1,"file_type":"directory","size":308}
+    {"path":"dream_cycle/polaris.txt","depth":2,"file_type":"file","size":308}
+    {"path":"lipsum","depth":1,"file_type":"directory","size":446}
+    {"path":"lipsum/lipsum.txt","depth":2,"file_type":"file","size":446}
+    {"path":"necronomicon.txt","depth":1,"file_type":"file","size":83}
+    {"path":"nemesis.txt","depth":1,"file_type":"file","size":161}
+    {"path":"nylarlathotep.txt","depth":1,"file_type":"file","size":100}
+    {"path":"the_yellow_king","depth":1,"file_type":"directory","size":143}
+    {"path":"the_yellow_king/cassildas_song.md","depth":2,"file_type":"file","size":143}"#
+);
+
+/// The `path` of a record.
+fn path(record: &str) -> &str {
+    record.split('"').nth(3).unwrap()
+}
+
+#[test]
+fn ndjson() {
+    let out = utils::run_cmd(&["--format", "ndjson", "tests/data"]);
+
+    let mut records = out.lines().collect::<Vec<_>>();
+    records.sort_unstable();
+
+    let mut expected = RECORDS.lines().collect::<Vec<_>>();
+    expected.sort_unstable();
+
+    assert_eq!(records, expected);
+}
+
+#[test]
+fn ndjson_contents_first() {
+    let out = utils::run_cmd(&["--format", "ndjson", "--threads", "4", "tests/data"]);
+    let records = out.lines().collect::<Vec<_>>();
+
+    assert_eq!(path(records[records.len() - 1]), ".", "{out}");
+
+    for (i, record) in records.iter().enumerate() {
+        let prefix = format!("{}/", path(record));
+
+        assert!(
+            records[i..]
+                .iter()
+                .all(|later| !path(later).starts_with(&prefix)),
+            "{out}"
+        );
+    }
+}
+
+#[test]
+fn ndjson_with_level() {
+    assert_eq!(
+        utils::run_cmd(&["--format", "ndjson", "--level", "0", "tests/data"]),
+        r#"{"path":".","depth":0,"file_type":"directory","size":1241}"#
+    )
+}
+
+#[test]
+fn ndjson_buffered() -> Result<(), Box<dyn Error>> {
+    let snapshot = NamedTempFile::new()?;
+    let path = snapshot.path().to_str().unwrap();
+
+    assert_eq!(
+        utils::run_cmd(&["--format", "ndjson", "--save", path, "tests/data"]),
+        RECORDS
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ndjson_hardlinks() -> Result<(), Box<dyn Error>> {
+    let dir = tempfile::tempdir()?;
+    let latest = dir.path().join("daily.0");
+    let previous = dir.path().join("daily.1");
+
+    fs::create_dir_all(&latest)?;
+    fs::create_dir_all(&previous)?;
+
+    fs::write(previous.join("a"), [0; 1000])?;
+    fs::write(latest.join("b"), [0; 10])?;
+    fs::hard_link(previous.join("a"), latest.join("a"))?;
+
+    let root = dir.path().to_str().unwrap();
+
+    let out = utils::run_cmd(&["--format", "ndjson", root]);
+
+    assert!(out.contains(r#"{"path":"daily.0","depth":1,"file_type":"directory","size":1010}"#));
+    assert!(out.contains(r#"{"path":"daily.1","depth":1,"file_type":"directory","size":null}"#));
+
+    let out = utils::run_cmd(&["--format", "ndjson", "--hardlinks", "split", root]);
+
+    assert!(out.contains(r#"{"path":"daily.0","depth":1,"file_type":"directory","size":510}"#));
+    assert!(out.contains(r#"{"path":"daily.1","depth":1,"file_type":"directory","size":500}"#));
+    assert!(out.ends_with(r#"{"path":".","depth":0,"file_type":"directory","size":1010}"#));
+
+    Ok(())
+}
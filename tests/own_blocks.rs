@@ -0,0 +1,32 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn own_blocks() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--import-ncdu",
+            "tests/ncdu/export.json",
+            "--disk-usage",
+            "physical",
+            "--own-blocks"
+        ]),
+        indoc!(
+            "- ┌─ sign
+            4096  B │  ┌─ cassilda.md
+            4096  B │  ├─ camilla.md
+            8192  B ├─ lake_hali
+            4096  B ├─ hastur.txt
+            16384 B carcosa
+
+            1 directory, 4 files"
+        )
+    )
+}
+
+#[test]
+#[should_panic(expected = "'--own-blocks' can only be used with")]
+fn own_blocks_logical() {
+    utils::run_cmd(&["--import-ncdu", "tests/ncdu/export.json", "--own-blocks"]);
+}
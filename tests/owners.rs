@@ -0,0 +1,132 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use super::utils;
+    use std::{error::Error, fs, process::Command};
+    use tempfile::NamedTempFile;
+
+    /// Name of the user running the tests, who is assumed to own the test data.
+    fn current_user() -> String {
+        let output = Command::new("id").arg("-un").output().unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn owners_user() {
+        assert_eq!(
+            utils::run_cmd(&["--owners", "user", "tests/data"]),
+            format!("1241 B 100.0% 6 files {}", current_user())
+        )
+    }
+
+    #[test]
+    fn owners_group() {
+        let out = utils::run_cmd(&["--owners", "group", "tests/data"]);
+
+        assert_eq!(out.lines().count(), 1, "{out}");
+        assert!(out.starts_with("1241 B 100.0% 6 files "), "{out}");
+    }
+
+    #[test]
+    fn owners_user_and_group() {
+        let user = current_user();
+        let expected =
+            format!("By user:\n1241 B 100.0% 6 files {user}\n\nBy group:\n1241 B 100.0% 6 files ");
+
+        let out = utils::run_cmd(&["--owners", "user,group", "tests/data"]);
+        assert!(out.starts_with(&expected), "{out}");
+
+        let out = utils::run_cmd(&["--owners", "user", "--owners", "group", "tests/data"]);
+        assert!(out.starts_with(&expected), "{out}");
+    }
+
+    #[test]
+    fn owners_load() -> Result<(), Box<dyn Error>> {
+        let snapshot = NamedTempFile::new()?;
+        let path = snapshot.path().to_str().unwrap();
+
+        utils::run_cmd(&["--save", path, "tests/data"]);
+
+        assert_eq!(
+            utils::run_cmd(&["--load", path, "--owners", "user"]),
+            format!("1241 B 100.0% 6 files {}", current_user())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn owners_import_ncdu() -> Result<(), Box<dyn Error>> {
+        let export = NamedTempFile::new()?;
+        let path = export.path().to_str().unwrap();
+
+        fs::write(path, utils::run_cmd(&["--format", "ncdu", "tests/data"]))?;
+
+        assert_eq!(
+            utils::run_cmd(&["--import-ncdu", path, "--owners", "user"]),
+            format!("1241 B 100.0% 6 files {}", current_user())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn owner_column() {
+        let user = current_user();
+        let out = utils::run_cmd(&["--long", "--owner", "user", "--level", "1", "tests/data"]);
+
+        // Skips the summary at the end.
+        for line in out.lines().filter(|line| line.contains(" B ")) {
+            match line.split_whitespace().nth(1) {
+                Some(perms) if perms.starts_with('d') => {
+                    assert!(line.contains(&format!(" {user} 100% ")), "{out}");
+                }
+                Some(perms) if perms.starts_with('.') => {
+                    assert!(line.contains(&format!(" {user}      ")), "{out}");
+                }
+                _ => (),
+            }
+        }
+    }
+
+    #[test]
+    fn owner_columns() {
+        let user = current_user();
+        let out = utils::run_cmd(&[
+            "--long",
+            "--owner",
+            "user,group",
+            "--level",
+            "1",
+            "tests/data",
+        ]);
+
+        // Skips the summary at the end.
+        for line in out.lines().filter(|line| line.contains(" B ")) {
+            match line.split_whitespace().nth(1) {
+                Some(perms) if perms.starts_with('d') => {
+                    assert!(line.contains(&format!(" {user} 100% ")), "{out}");
+                    assert_eq!(line.matches(" 100% ").count(), 2, "{out}");
+                }
+                Some(perms) if perms.starts_with('.') => {
+                    assert!(line.contains(&format!(" {user}      ")), "{out}");
+                    assert_eq!(line.matches("      ").count(), 2, "{out}");
+                }
+                _ => (),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "--long")]
+    fn owner_requires_long() {
+        utils::run_cmd(&["--owner", "user", "tests/data"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn owners_conflicts_with_breakdown() {
+        utils::run_cmd(&["--owners", "user", "--breakdown", "ext", "tests/data"]);
+    }
+}
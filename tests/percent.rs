@@ -0,0 +1,45 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn percent() {
+    assert_eq!(
+        utils::run_cmd(&["--percent", "tests/data"]),
+        indoc!(
+            "143  B 100.0%  11.5% ██▎                     ┌─ cassildas_song.md
+            143  B  11.5%  11.5% ██▎░░░░░░░░░░░░░░░░░ ┌─ the_yellow_king
+            100  B   8.1%   8.1% █▋░░░░░░░░░░░░░░░░░░ ├─ nylarlathotep.txt
+            161  B  13.0%  13.0% ██▋░░░░░░░░░░░░░░░░░ ├─ nemesis.txt
+            83   B   6.7%   6.7% █▍░░░░░░░░░░░░░░░░░░ ├─ necronomicon.txt
+            446  B 100.0%  35.9% ███████▎             │  ┌─ lipsum.txt
+            446  B  35.9%  35.9% ███████▎░░░░░░░░░░░░ ├─ lipsum
+            308  B 100.0%  24.8% █████                │  ┌─ polaris.txt
+            308  B  24.8%  24.8% █████░░░░░░░░░░░░░░░ ├─ dream_cycle
+            1241 B 100.0% 100.0% ████████████████████ data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn percent_flat() {
+    assert_eq!(
+        utils::run_cmd(&["--percent", "--flat", "tests/data"]),
+        indoc!(
+            "1241 B   100.0% 100.0% ████████████████████ data
+            308  B    24.8%  24.8% █████░░░░░░░░░░░░░░░ dream_cycle
+            308  B   100.0%  24.8% █████                dream_cycle/polaris.txt
+            446  B    35.9%  35.9% ███████▎░░░░░░░░░░░░ lipsum
+            446  B   100.0%  35.9% ███████▎             lipsum/lipsum.txt
+            83   B     6.7%   6.7% █▍░░░░░░░░░░░░░░░░░░ necronomicon.txt
+            161  B    13.0%  13.0% ██▋░░░░░░░░░░░░░░░░░ nemesis.txt
+            100  B     8.1%   8.1% █▋░░░░░░░░░░░░░░░░░░ nylarlathotep.txt
+            143  B    11.5%  11.5% ██▎░░░░░░░░░░░░░░░░░ the_yellow_king
+            143  B   100.0%  11.5% ██▎                  the_yellow_king/cassildas_song.md
+
+            3 directories, 6 files"
+        )
+    )
+}
@@ -0,0 +1,82 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn min_size() {
+    assert_eq!(
+        utils::run_cmd(&["--min-size", "150", "tests/data"]),
+        indoc!(
+            "161  B ┌─ nemesis.txt
+            446  B │  ┌─ lipsum.txt
+            446  B ├─ lipsum
+            308  B │  ┌─ polaris.txt
+            308  B ├─ dream_cycle
+            1241 B data
+
+            2 directories, 3 files"
+        )
+    )
+}
+
+#[test]
+fn min_size_counts() {
+    assert_eq!(
+        utils::run_cmd(&["--min-size", "150", "--counts", "tests/data"]),
+        indoc!(
+            "161  B                        ┌─ nemesis.txt
+            446  B                        │  ┌─ lipsum.txt
+            446  B 1 file                 ├─ lipsum
+            308  B                        │  ┌─ polaris.txt
+            308  B 1 file                 ├─ dream_cycle
+            1241 B 3 files 2 dirs         data
+
+            2 directories, 3 files"
+        )
+    )
+}
+
+#[test]
+fn max_size() {
+    assert_eq!(
+        utils::run_cmd(&["--max-size", "200", "tests/data"]),
+        indoc!(
+            "143  B    ┌─ cassildas_song.md
+            143  B ┌─ the_yellow_king
+            100  B ├─ nylarlathotep.txt
+            161  B ├─ nemesis.txt
+            83   B ├─ necronomicon.txt
+            1241 B data
+
+            1 directory, 4 files"
+        )
+    )
+}
+
+#[test]
+fn size_range() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--min-size",
+            "120",
+            "--max-size",
+            "0.2K",
+            "--flat",
+            "tests/data"
+        ]),
+        indoc!(
+            "1241 B   data
+            161  B   nemesis.txt
+            143  B   the_yellow_king
+            143  B   the_yellow_king/cassildas_song.md
+
+            1 directory, 2 files"
+        )
+    )
+}
+
+#[test]
+#[should_panic(expected = "unknown unit")]
+fn size_of_unknown_unit() {
+    utils::run_cmd(&["--min-size", "1X", "tests/data"]);
+}
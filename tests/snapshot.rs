@@ -0,0 +1,121 @@
+use indoc::indoc;
+use std::error::Error;
+use tempfile::NamedTempFile;
+
+mod utils;
+
+#[test]
+fn snapshot_round_trip() -> Result<(), Box<dyn Error>> {
+    let snapshot = NamedTempFile::new()?;
+    let path = snapshot.path().to_str().unwrap();
+
+    let out = utils::run_cmd(&["--save", path, "tests/data"]);
+
+    assert_eq!(out, utils::run_cmd(&["--load", path]));
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_rerender() -> Result<(), Box<dyn Error>> {
+    let snapshot = NamedTempFile::new()?;
+    let path = snapshot.path().to_str().unwrap();
+
+    utils::run_cmd(&["--save", path, "tests/data"]);
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--load",
+            path,
+            "--sort",
+            "size",
+            "--pattern",
+            "*.txt",
+            "--glob"
+        ]),
+        indoc!(
+            "83   B ┌─ necronomicon.txt
+            100  B ├─ nylarlathotep.txt
+            161  B ├─ nemesis.txt
+            308  B │  ┌─ polaris.txt
+            308  B ├─ dream_cycle
+            446  B │  ┌─ lipsum.txt
+            446  B ├─ lipsum
+            1098 B data
+
+            2 directories, 5 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_unfiltered() -> Result<(), Box<dyn Error>> {
+    let snapshot = NamedTempFile::new()?;
+    let path = snapshot.path().to_str().unwrap();
+
+    utils::run_cmd(&[
+        "--save",
+        path,
+        "--min-size",
+        "150",
+        "--collapse",
+        "40%",
+        "tests/data",
+    ]);
+
+    assert_eq!(
+        utils::run_cmd(&["--load", path]),
+        utils::run_cmd(&["tests/data"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_hidden() -> Result<(), Box<dyn Error>> {
+    let snapshot = NamedTempFile::new()?;
+    let path = snapshot.path().to_str().unwrap();
+
+    let out = utils::run_cmd(&["--save", path, "--hidden", "tests/data"]);
+
+    assert_eq!(out, utils::run_cmd(&["--load", path, "--hidden"]));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "taken without '--hidden'")]
+fn snapshot_without_hidden() {
+    let snapshot = NamedTempFile::new().unwrap();
+    let path = snapshot.path().to_str().unwrap();
+
+    utils::run_cmd(&["--save", path, "tests/data"]);
+    utils::run_cmd(&["--load", path, "--hidden"]);
+}
+
+#[test]
+#[should_panic(expected = "taken without '--no-ignore'")]
+fn snapshot_without_no_ignore() {
+    let snapshot = NamedTempFile::new().unwrap();
+    let path = snapshot.path().to_str().unwrap();
+
+    utils::run_cmd(&["--save", path, "tests/data"]);
+    utils::run_cmd(&["--load", path, "--no-ignore"]);
+}
+
+#[test]
+#[should_panic(expected = "--pattern")]
+fn snapshot_pattern() {
+    let snapshot = NamedTempFile::new().unwrap();
+    let path = snapshot.path().to_str().unwrap();
+
+    utils::run_cmd(&["--save", path, "--pattern", "*.txt", "--glob", "tests/data"]);
+}
+
+#[test]
+#[should_panic]
+fn snapshot_invalid() {
+    utils::run_cmd(&["--load", "tests/data/nemesis.txt"]);
+}
@@ -0,0 +1,19 @@
+mod utils;
+
+#[test]
+fn svg() {
+    let out = utils::run_cmd(&["--format", "svg", "tests/data"]);
+
+    assert!(out.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(out.ends_with("</svg>"));
+    assert!(out.contains("tests/data/dream_cycle/polaris.txt&#10;308 B</title>"));
+    assert_eq!(out.matches("<rect").count(), 10);
+}
+
+#[test]
+fn svg_with_level() {
+    let out = utils::run_cmd(&["--format", "svg", "--level", "1", "tests/data"]);
+
+    assert!(out.contains("tests/data/dream_cycle&#10;308 B</title>"));
+    assert!(!out.contains("polaris.txt"));
+}
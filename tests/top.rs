@@ -0,0 +1,38 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn top() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "3", "tests/data"]),
+        indoc!(
+            "1. 446  B   lipsum/lipsum.txt
+            2. 308  B   dream_cycle/polaris.txt
+            3. 161  B   nemesis.txt"
+        )
+    )
+}
+
+#[test]
+fn top_dirs() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "2", "--top-dirs", "tests/data"]),
+        indoc!(
+            "1. 446  B   lipsum
+            2. 308  B   dream_cycle"
+        )
+    )
+}
+
+#[test]
+fn top_with_pattern() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "10", "--pattern", "n.*\\.txt", "tests/data"]),
+        indoc!(
+            "1. 161 B   nemesis.txt
+            2. 100 B   nylarlathotep.txt
+            3. 83  B   necronomicon.txt"
+        )
+    )
+}